@@ -36,6 +36,57 @@ fn main() {
 }
 */
 
+// NOTE: Not in book
+// With the PartialOrd trait bound, we can write a working version of largest() that finds both the smallest and largest items
+// Returning references means T doesn't need to implement Copy or Clone, and we only need to walk the list once
+// An empty list has no smallest or largest item, so we return an Option instead of indexing list[0] and panicking
+fn min_max<T: PartialOrd>(list: &[T]) -> Option<(&T, &T)> {
+    let (first, rest) = list.split_first()?;
+    let mut min = first;
+    let mut max = first;
+
+    for item in rest {
+        // Strict comparisons keep the first of any equal items, so ties are resolved the same way every time
+        if item < min {
+            min = item;
+        }
+        if item > max {
+            max = item;
+        }
+    }
+    Some((min, max))
+}
+
+// NOTE: Not in book
+// min_max() needs T to implement PartialOrd, which only gives one way to order the items
+// max_by() and min_by() take the comparison as a closure instead, so T doesn't need any trait bounds,
+// and the same items can be ordered in different ways (e.g. people by age in one call and by name in the next)
+// `cmp` returns an Ordering (Less, Equal or Greater), like the cmp() method on integers and strings
+// Like min_max(), the first of any equal items is returned
+use std::cmp::Ordering;
+
+fn max_by<T>(items: &[T], cmp: impl Fn(&T, &T) -> Ordering) -> Option<&T> {
+    let (first, rest) = items.split_first()?;
+    let mut max = first;
+    for item in rest {
+        if cmp(item, max) == Ordering::Greater {
+            max = item;
+        }
+    }
+    Some(max)
+}
+
+fn min_by<T>(items: &[T], cmp: impl Fn(&T, &T) -> Ordering) -> Option<&T> {
+    let (first, rest) = items.split_first()?;
+    let mut min = first;
+    for item in rest {
+        if cmp(item, min) == Ordering::Less {
+            min = item;
+        }
+    }
+    Some(min)
+}
+
 //
 // In struct Definitions
 //
@@ -166,6 +217,86 @@ impl<X1, Y1> Point<X1, Y1> {
             y: other.y,
         }
     }
+
+    // NOTE: Not in book
+    // swap doesn't need any new generic parameters, but the return type lists X1 and Y1 in the opposite order
+    // A Point<i32, &str> becomes a Point<&str, i32>, so the types move along with the values
+    // swap takes ownership of self, so the fields can be moved into the new point without needing Copy or Clone
+    fn swap(self) -> Point<Y1, X1> {
+        Point {
+            x: self.y,
+            y: self.x,
+        }
+    }
+}
+
+// NOTE: Not in book
+// Like the Point<f32> example above, this impl block only applies to points where both coordinates are f64
+// A Point<i32, &str> won't have these methods, since subtracting or squaring a string slice doesn't make sense
+impl Point<f64, f64> {
+    fn distance_from_origin(&self) -> f64 {
+        self.distance_to(&Point { x: 0.0, y: 0.0 })
+    }
+
+    fn distance_to(&self, other: &Point<f64, f64>) -> f64 {
+        // hypot() computes sqrt(dx^2 + dy^2) without overflowing on large values
+        (self.x - other.x).hypot(self.y - other.y)
+    }
+}
+
+//
+// Const Generics
+//
+
+// NOTE: Not in book
+// Generics can also be parameterized over constant values, not just types
+// `const N: usize` is a value that is known at compile time, so FixedBuffer<i32, 3> and FixedBuffer<i32, 4> are two different types
+// The array's size is part of its type, so the buffer lives inline (e.g. on the stack) and never needs to allocate or grow
+// Each slot is an Option<T>, since an array needs a value in every slot even before anything has been pushed
+struct FixedBuffer<T, const N: usize> {
+    slots: [Option<T>; N],
+    len: usize,
+}
+
+impl<T, const N: usize> FixedBuffer<T, N> {
+    fn new() -> Self {
+        // `[None; N]` would need T to implement Copy, from_fn() creates each slot separately instead
+        FixedBuffer {
+            slots: std::array::from_fn(|_| None),
+            len: 0,
+        }
+    }
+
+    // A full buffer can't grow, so the item is handed back in the Err instead of being dropped
+    fn push(&mut self, item: T) -> Result<(), T> {
+        if self.len == N {
+            return Err(item);
+        }
+        self.slots[self.len] = Some(item);
+        self.len += 1;
+        Ok(())
+    }
+
+    // The slots are filled from the front, so only the first `len` need to be looked at
+    // flatten() skips over the None values, and turns each &Option<T> into a &T
+    fn iter(&self) -> impl Iterator<Item = &T> {
+        self.slots[..self.len].iter().flatten()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    // N is available as a value inside the impl block, just like a function parameter
+    fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<T, const N: usize> Default for FixedBuffer<T, N> {
+    fn default() -> Self {
+        FixedBuffer::new()
+    }
 }
 
 fn main() {
@@ -178,7 +309,206 @@ fn main() {
 
     println!("p3.x = {}, p3.y = {}", p3.x, p3.y);
 
+    // Note: not in book
+    // p4 is a Point<char, i32>, since p3 was a Point<i32, char>
+    let p4 = p3.swap();
+    println!("p4.x = {}, p4.y = {}", p4.x, p4.y);
+
+    // Note: not in book
+    // min_max works with any type that can be compared, just like largest
+    let number_list = vec![34, 50, 25, 100, 65];
+    if let Some((min, max)) = min_max(&number_list) {
+        println!("The smallest number is {min}, the largest number is {max}");
+    }
+
+    // Note: not in book
+    // The words are compared by length rather than alphabetically
+    let words = ["generic", "type", "parameters"];
+    println!(
+        "Longest word: {:?}, shortest word: {:?}",
+        max_by(&words, |a, b| a.len().cmp(&b.len())),
+        min_by(&words, |a, b| a.len().cmp(&b.len()))
+    );
+
+    // Note: not in book
+    // The capacity is part of the type, so it goes in the angle brackets along with the item type
+    let mut buffer: FixedBuffer<&str, 2> = FixedBuffer::new();
+    for word in ["one", "two", "three"] {
+        if let Err(word) = buffer.push(word) {
+            println!(
+                "No room for {word}, the buffer only holds {}",
+                buffer.capacity()
+            );
+        }
+    }
+    println!(
+        "The buffer holds {} words: {:?}",
+        buffer.len(),
+        buffer.iter().collect::<Vec<_>>()
+    );
+
     // Note: not in book
     // This won't work, as p2 has different types for x and y than p1
     //let p4 = p1.mixupSameType(p2);
+
+    // Note: not in book
+    // distance_to() is only available since both points are Point<f64, f64>
+    let origin = Point { x: 0.0, y: 0.0 };
+    let corner = Point { x: 3.0, y: 4.0 };
+    println!(
+        "corner is {} away from the origin",
+        corner.distance_to(&origin)
+    );
+    println!(
+        "distance_from_origin() agrees: {}",
+        corner.distance_from_origin()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn min_max_of_integers() {
+        let list = vec![34, 50, 25, 100, 65];
+        assert_eq!(min_max(&list), Some((&25, &100)));
+    }
+
+    #[test]
+    fn min_max_of_floats() {
+        let list = [2.5, -1.0, 7.25, 0.0];
+        assert_eq!(min_max(&list), Some((&-1.0, &7.25)));
+    }
+
+    #[test]
+    fn min_max_of_single_element() {
+        let list = ['q'];
+        let (min, max) = min_max(&list).unwrap();
+        // Both references point at the only element
+        assert!(std::ptr::eq(min, max));
+        assert_eq!(*min, 'q');
+    }
+
+    #[test]
+    fn min_max_of_empty_slice() {
+        let list: [i32; 0] = [];
+        assert_eq!(min_max(&list), None);
+    }
+
+    #[test]
+    fn distance_from_origin_of_3_4_5_triangle() {
+        let p = Point { x: 3.0, y: 4.0 };
+        assert!((p.distance_from_origin() - 5.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn distance_between_points() {
+        let p1 = Point { x: 1.0, y: -1.0 };
+        let p2 = Point { x: -2.0, y: 3.0 };
+        assert!((p1.distance_to(&p2) - 5.0).abs() < f64::EPSILON);
+        // Distance is the same in both directions
+        assert!((p2.distance_to(&p1) - 5.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn swap_exchanges_values_and_types() {
+        let p = Point { x: 5, y: "five" };
+        // The annotation only compiles if swap() also swapped the field types
+        let swapped: Point<&str, i32> = p.swap();
+        assert_eq!(swapped.x, "five");
+        assert_eq!(swapped.y, 5);
+    }
+
+    #[test]
+    fn min_max_ties_return_first_items() {
+        let list = [3, 1, 5, 1, 5];
+        let (min, max) = min_max(&list).unwrap();
+        assert!(std::ptr::eq(min, &list[1]));
+        assert!(std::ptr::eq(max, &list[2]));
+    }
+
+    #[test]
+    fn fixed_buffer_fills_up_to_capacity() {
+        let mut buffer: FixedBuffer<String, 3> = FixedBuffer::new();
+        assert_eq!(buffer.len(), 0);
+        assert_eq!(buffer.capacity(), 3);
+
+        for word in ["a", "b", "c"] {
+            assert_eq!(buffer.push(String::from(word)), Ok(()));
+        }
+
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.iter().collect::<Vec<_>>(), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn fixed_buffer_rejects_push_when_full() {
+        let mut buffer = FixedBuffer::<i32, 2>::default();
+        buffer.push(1).unwrap();
+        buffer.push(2).unwrap();
+
+        // The rejected item is handed back, and the buffer is unchanged
+        assert_eq!(buffer.push(3), Err(3));
+        assert_eq!(buffer.iter().collect::<Vec<_>>(), vec![&1, &2]);
+    }
+
+    #[test]
+    fn fixed_buffer_of_zero() {
+        let mut buffer: FixedBuffer<char, 0> = FixedBuffer::new();
+
+        assert_eq!(buffer.push('x'), Err('x'));
+        assert_eq!(buffer.iter().count(), 0);
+    }
+
+    // Doesn't implement PartialOrd, so it can only be compared with a closure
+    struct Rectangle {
+        width: u32,
+        height: u32,
+    }
+
+    fn by_area(a: &Rectangle, b: &Rectangle) -> Ordering {
+        (a.width * a.height).cmp(&(b.width * b.height))
+    }
+
+    #[test]
+    fn max_by_area() {
+        let rects = [
+            Rectangle {
+                width: 10,
+                height: 10,
+            },
+            Rectangle {
+                width: 30,
+                height: 5,
+            },
+            Rectangle {
+                width: 2,
+                height: 40,
+            },
+        ];
+
+        let largest = max_by(&rects, by_area).unwrap();
+        assert_eq!((largest.width, largest.height), (30, 5));
+
+        let smallest = min_by(&rects, by_area).unwrap();
+        assert_eq!((smallest.width, smallest.height), (2, 40));
+    }
+
+    #[test]
+    fn max_by_and_min_by_return_first_of_ties() {
+        let list = ["bb", "a", "cc", "d"];
+        let by_len = |a: &&str, b: &&str| a.len().cmp(&b.len());
+
+        assert!(std::ptr::eq(max_by(&list, by_len).unwrap(), &list[0]));
+        assert!(std::ptr::eq(min_by(&list, by_len).unwrap(), &list[1]));
+    }
+
+    #[test]
+    fn max_by_and_min_by_of_empty_slice() {
+        let rects: [Rectangle; 0] = [];
+
+        assert!(max_by(&rects, by_area).is_none());
+        assert!(min_by(&rects, by_area).is_none());
+    }
 }
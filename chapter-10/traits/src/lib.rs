@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Display;
 // Traits define functionality that is shared between types in an abstract way
@@ -37,6 +38,20 @@ pub trait Summary {
 
     // A summarize method signature that the types implement
     fn summarize(&self) -> String;
+
+    // Note: not in book
+    // A shorter version of summarize() for when there isn't much room (see Default Implementations below)
+    // By default, it cuts summarize() down to its first 20 characters
+    // Counting chars() instead of bytes means a multi-byte character won't be cut in half
+    fn short_summary(&self) -> String {
+        let summary = self.summarize();
+        if summary.chars().count() <= 20 {
+            summary
+        } else {
+            let short: String = summary.chars().take(20).collect();
+            format!("{short}...")
+        }
+    }
 }
 
 //
@@ -49,6 +64,44 @@ impl Summary for NewsArticle {
     fn summarize(&self) -> String {
         format!("{}, {} ({})", self.headline, self.author, self.location)
     }
+
+    // Note: not in book
+    // Types can override a default implementation, the headline is already a good short summary for an article
+    fn short_summary(&self) -> String {
+        self.headline_summary().to_string()
+    }
+}
+
+// Note: not in book
+// Methods defined directly on a type can be used alongside the methods from a trait
+// headline_summary() returns a &str that borrows from the article, so it doesn't need to allocate a new String like summarize() does
+impl NewsArticle {
+    pub fn headline_summary(&self) -> &str {
+        &self.headline
+    }
+}
+
+impl Tweet {
+    // A retweet of a reply is still reported as a retweet, since the content came from someone else
+    pub fn kind(&self) -> &'static str {
+        if self.retweet {
+            "retweet"
+        } else if self.reply {
+            "reply"
+        } else {
+            "original"
+        }
+    }
+}
+
+// Note: not in book
+// Returns references to the tweets that are neither replies nor retweets
+// The returned references borrow from `tweets`, so no Tweet needs to be cloned
+pub fn original_tweets(tweets: &[Tweet]) -> Vec<&Tweet> {
+    tweets
+        .iter()
+        .filter(|tweet| !tweet.reply && !tweet.retweet)
+        .collect()
 }
 
 impl Summary for Tweet {
@@ -254,12 +307,485 @@ impl<T: Display + PartialOrd> Pair<T> {
     }
 }
 
+// Note: not in book
+// The same idea works with more than two values
+// `Trio<T>` can hold any type, but largest() and cmp_display() only exist when T implements both Display and PartialOrd
+pub struct Trio<T> {
+    x: T,
+    y: T,
+    z: T,
+}
+
+impl<T> Trio<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Display + PartialOrd> Trio<T> {
+    /// Returns a reference to the largest of the three values
+    ///
+    /// Types that don't implement `Display` and `PartialOrd` don't get this method:
+    ///
+    /// ```compile_fail
+    /// struct NoBounds;
+    ///
+    /// let trio = traits::Trio::new(NoBounds, NoBounds, NoBounds);
+    /// trio.largest();
+    /// ```
+    pub fn largest(&self) -> &T {
+        let mut largest = &self.x;
+        for item in [&self.y, &self.z] {
+            if item > largest {
+                largest = item;
+            }
+        }
+        largest
+    }
+
+    /// Prints the largest of the three values
+    ///
+    /// ```compile_fail
+    /// // Vec<i32> implements PartialOrd, but not Display
+    /// let trio = traits::Trio::new(vec![1], vec![2], vec![3]);
+    /// trio.cmp_display();
+    /// ```
+    pub fn cmp_display(&self) {
+        println!("The largest member is {}", self.largest());
+    }
+}
+
 // We can also conditionally implement a trait for any type that implements another trait
 // This is called a blacket implementation
 
 // As a in-language example, the standard library implements the ToString trait on any type that implements the Display trait
+/*
 impl<T: Display> ToString for T {}
+*/
 
 // Because of this, we can call to_string() for any type that implements the Display trait
+/*
 let s = 3.to_string();
+*/
+
+//
+// Formatting Anything that Implements Display
+//
+
+// Note: not in book
+// print_table() lays out rows of cells in aligned columns, e.g.
+// name   | age
+// Ferris | 9
+// The Display bound is all it needs, since each cell only has to be turned into text with to_string() (the blanket implementation from above)
+// Every column is padded to its widest cell, counting chars rather than bytes so multi-byte characters don't throw off the alignment
+// Rows can have different numbers of cells, and trailing spaces are trimmed from the end of each line
+pub fn print_table<T: Display>(rows: &[Vec<T>]) -> String {
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+        .collect();
+
+    let mut widths: Vec<usize> = Vec::new();
+    for row in &cells {
+        for (column, cell) in row.iter().enumerate() {
+            let width = cell.chars().count();
+            match widths.get_mut(column) {
+                Some(widest) => *widest = (*widest).max(width),
+                None => widths.push(width),
+            }
+        }
+    }
+
+    cells
+        .iter()
+        .map(|row| {
+            row.iter()
+                .zip(&widths)
+                // `width$` takes the padding width from the `width` argument, and `<` pads on the right
+                .map(|(cell, &width)| format!("{cell:<width$}"))
+                .collect::<Vec<String>>()
+                .join(" | ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+//
+// Storing Different Summary Types Together
+//
+
+// Note: not in book (trait objects are covered in chapter 18)
+// Generics with trait bounds (e.g. `Vec<T>` where `T: Summary`) can only hold one concrete type at a time
+// To hold both `Tweet`s and `NewsArticle`s in the same vector, we can store trait objects with `Box<dyn Summary>` instead
+// Each item is a pointer to some type that implements Summary, and summarize() is looked up at runtime
+pub struct Feed {
+    items: Vec<Box<dyn Summary>>,
+}
+
+impl Feed {
+    pub fn new() -> Feed {
+        Feed { items: Vec::new() }
+    }
+
+    pub fn add(&mut self, item: Box<dyn Summary>) {
+        self.items.push(item);
+    }
+
+    // Each summary is put on its own line, in the order the items were added
+    pub fn render(&self) -> String {
+        self.items
+            .iter()
+            .map(|item| item.summarize())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    // Like render(), but the shortest summaries come first
+    // The length is counted in characters (like short_summary() and print_table()), so a summary with 'é' in it isn't longer than one with 'e'
+    // The summaries are computed once up front, rather than calling summarize() every time sort_by_key() compares two items
+    // sort_by_key() is a stable sort, so summaries with the same length stay in the order they were added
+    pub fn render_sorted(&self) -> String {
+        let mut summaries: Vec<String> = self.items.iter().map(|item| item.summarize()).collect();
+        summaries.sort_by_key(|summary| summary.chars().count());
+        summaries.join("\n")
+    }
+
+    // to_lowercase() is called on both sides, so "rust", "Rust" and "RUST" all match each other
+    pub fn matching(&self, keyword: &str) -> Vec<String> {
+        let keyword = keyword.to_lowercase();
+        self.items
+            .iter()
+            .map(|item| item.summarize())
+            .filter(|summary| summary.to_lowercase().contains(&keyword))
+            .collect()
+    }
+}
+
+impl Default for Feed {
+    fn default() -> Self {
+        Feed::new()
+    }
+}
 
+// Unlike Feed, summarize_all() doesn't take ownership of the items, it only needs references to them
+// `&dyn Summary` is a trait object behind a reference instead of a Box, so the slice can point to items that are owned elsewhere
+pub fn summarize_all(items: &[&dyn Summary]) -> Vec<String> {
+    items.iter().map(|item| item.summarize()).collect()
+}
+
+//
+// Indexing Summaries by Keyword
+//
+
+// Note: not in book (hash maps are covered in chapter 8)
+// Feed::matching() calls summarize() on every item each time it searches
+// An Index does that work once as each item is added, by mapping each word in the item's summary to the positions of the items that contain it
+// The items themselves aren't stored, only their positions (0 for the first item added, 1 for the second, etc.)
+pub struct Index {
+    words: HashMap<String, Vec<usize>>,
+    len: usize,
+}
+
+impl Index {
+    pub fn new() -> Index {
+        Index {
+            words: HashMap::new(),
+            len: 0,
+        }
+    }
+
+    // Accepts any type that implements Summary, the item only needs to be borrowed while its summary is indexed
+    // Returns the position the item was given
+    pub fn add(&mut self, item: &impl Summary) -> usize {
+        let position = self.len;
+        self.len += 1;
+
+        // Punctuation isn't part of a word, so "course," and "course" are both indexed as "course"
+        for word in item
+            .summarize()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+        {
+            let positions = self.words.entry(word.to_lowercase()).or_default();
+            // A word can show up more than once in the same summary, but the item should only be listed once
+            if positions.last() != Some(&position) {
+                positions.push(position);
+            }
+        }
+
+        position
+    }
+
+    // Returns the positions of the items whose summary contains `word`, ignoring case, in the order the items were added
+    // A word that isn't in any summary returns an empty slice instead of None, since there is nothing to handle differently
+    pub fn search(&self, word: &str) -> &[usize] {
+        self.words
+            .get(&word.to_lowercase())
+            .map_or(&[], |positions| positions.as_slice())
+    }
+}
+
+impl Default for Index {
+    fn default() -> Self {
+        Index::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trio_largest_of_integers() {
+        assert_eq!(*Trio::new(1, 2, 3).largest(), 3);
+        assert_eq!(*Trio::new(3, 2, 1).largest(), 3);
+        assert_eq!(*Trio::new(-5, 10, -20).largest(), 10);
+    }
+
+    #[test]
+    fn trio_largest_returns_first_of_ties() {
+        let trio = Trio::new(7, 7, 7);
+        assert!(std::ptr::eq(trio.largest(), &trio.x));
+    }
+
+    #[test]
+    fn trio_largest_of_strings() {
+        let trio = Trio::new("apple", "pear", "banana");
+        assert_eq!(*trio.largest(), "pear");
+        trio.cmp_display();
+    }
+
+    fn tweet(username: &str, content: &str) -> Tweet {
+        Tweet {
+            username: String::from(username),
+            content: String::from(content),
+            reply: false,
+            retweet: false,
+        }
+    }
+
+    fn article(headline: &str, author: &str, location: &str) -> NewsArticle {
+        NewsArticle {
+            headline: String::from(headline),
+            location: String::from(location),
+            author: String::from(author),
+            content: String::new(),
+        }
+    }
+
+    #[test]
+    fn feed_renders_in_insertion_order() {
+        let mut feed = Feed::new();
+        feed.add(Box::new(tweet("horse_ebooks", "of course")));
+        feed.add(Box::new(article(
+            "Penguins win the Stanley Cup Championship!",
+            "Iceburgh",
+            "Pittsburgh, PA, USA",
+        )));
+        feed.add(Box::new(tweet("rustlang", "Rust 2024 is here")));
+
+        assert_eq!(
+            feed.render(),
+            "horse_ebooks: of course\n\
+             Penguins win the Stanley Cup Championship!, Iceburgh (Pittsburgh, PA, USA)\n\
+             rustlang: Rust 2024 is here"
+        );
+    }
+
+    #[test]
+    fn empty_feed_renders_nothing() {
+        assert_eq!(Feed::new().render(), "");
+    }
+
+    #[test]
+    fn feed_matching_is_case_insensitive() {
+        let mut feed = Feed::new();
+        feed.add(Box::new(tweet("rustlang", "Rust 2024 is here")));
+        feed.add(Box::new(tweet("horse_ebooks", "of course")));
+        feed.add(Box::new(article(
+            "Why I TRUST the borrow checker",
+            "Ferris",
+            "Rustville",
+        )));
+        feed.add(Box::new(article("Penguins win", "Iceburgh", "Pittsburgh")));
+
+        assert_eq!(
+            feed.matching("RUST"),
+            vec![
+                "rustlang: Rust 2024 is here",
+                "Why I TRUST the borrow checker, Ferris (Rustville)",
+            ]
+        );
+        assert_eq!(feed.matching("hockey"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn feed_render_sorted_by_length() {
+        let mut feed = Feed::new();
+        // "alice: a longer tweet" is 21 characters
+        feed.add(Box::new(tweet("alice", "a longer tweet")));
+        // "bob: hi" is 7 characters
+        feed.add(Box::new(tweet("bob", "hi")));
+        // "Headline, Me (Here)" is 19 characters
+        feed.add(Box::new(article("Headline", "Me", "Here")));
+        // "eve: yo" is also 7 characters, and stays after "bob: hi"
+        feed.add(Box::new(tweet("eve", "yo")));
+
+        assert_eq!(
+            feed.render_sorted(),
+            "bob: hi\neve: yo\nHeadline, Me (Here)\nalice: a longer tweet"
+        );
+        // render() still uses insertion order
+        assert!(feed.render().starts_with("alice: a longer tweet"));
+    }
+
+    #[test]
+    fn feed_render_sorted_counts_characters() {
+        let mut feed = Feed::new();
+        // "bob: hiya" is 9 characters and 9 bytes
+        feed.add(Box::new(tweet("bob", "hiya")));
+        // "zoë: héé" is 8 characters, but 11 bytes, since 'ë' and 'é' are 2 bytes each
+        feed.add(Box::new(tweet("zoë", "héé")));
+        // "eve: hey" is also 8 characters, and stays after "zoë: héé"
+        feed.add(Box::new(tweet("eve", "hey")));
+
+        assert_eq!(feed.render_sorted(), "zoë: héé\neve: hey\nbob: hiya");
+    }
+
+    #[test]
+    fn news_article_summaries() {
+        let article = article(
+            "Penguins win the Stanley Cup Championship!",
+            "Iceburgh",
+            "Pittsburgh",
+        );
+
+        assert_eq!(
+            article.headline_summary(),
+            "Penguins win the Stanley Cup Championship!"
+        );
+        // NewsArticle overrides short_summary(), so it isn't cut down to 20 characters
+        assert_eq!(
+            article.short_summary(),
+            "Penguins win the Stanley Cup Championship!"
+        );
+    }
+
+    #[test]
+    fn tweet_default_short_summary() {
+        // "horse_ebooks: of course, as you probably already know" is longer than 20 characters
+        let long = tweet("horse_ebooks", "of course, as you probably already know");
+        assert_eq!(long.short_summary(), "horse_ebooks: of cou...");
+
+        // Short summaries are left as they are
+        let short = tweet("bob", "hi");
+        assert_eq!(short.short_summary(), "bob: hi");
+    }
+
+    fn tweet_with(content: &str, reply: bool, retweet: bool) -> Tweet {
+        Tweet {
+            reply,
+            retweet,
+            ..tweet("horse_ebooks", content)
+        }
+    }
+
+    #[test]
+    fn tweet_kinds() {
+        assert_eq!(tweet_with("hello", false, false).kind(), "original");
+        assert_eq!(tweet_with("@bob hi", true, false).kind(), "reply");
+        assert_eq!(tweet_with("RT hello", false, true).kind(), "retweet");
+        assert_eq!(tweet_with("RT @bob hi", true, true).kind(), "retweet");
+    }
+
+    #[test]
+    fn filters_original_tweets() {
+        let tweets = vec![
+            tweet_with("first", false, false),
+            tweet_with("@bob hi", true, false),
+            tweet_with("RT hello", false, true),
+            tweet_with("second", false, false),
+            tweet_with("RT @bob hi", true, true),
+        ];
+
+        let originals = original_tweets(&tweets);
+
+        assert_eq!(originals.len(), 2);
+        assert_eq!(originals[0].content, "first");
+        assert_eq!(originals[1].content, "second");
+        assert!(originals.iter().all(|tweet| tweet.kind() == "original"));
+    }
+
+    #[test]
+    fn summarizes_borrowed_items() {
+        let tweet = tweet("horse_ebooks", "of course");
+        let article = article("Penguins win", "Iceburgh", "Pittsburgh");
+
+        let summaries = summarize_all(&[&tweet, &article]);
+
+        assert_eq!(
+            summaries,
+            vec![
+                "horse_ebooks: of course",
+                "Penguins win, Iceburgh (Pittsburgh)"
+            ]
+        );
+        // The items were only borrowed, so they can still be used
+        assert_eq!(tweet.username, "horse_ebooks");
+        assert_eq!(article.author, "Iceburgh");
+    }
+
+    #[test]
+    fn index_finds_word_in_two_tweets() {
+        let mut index = Index::new();
+        index.add(&tweet("rustlang", "Rust 2024 is here"));
+        index.add(&tweet("horse_ebooks", "of course"));
+        index.add(&tweet("ferris", "I love rust, rust, RUST!"));
+        index.add(&tweet("bob", "Trust me"));
+
+        // "Trust" contains "rust", but it's a different word
+        assert_eq!(index.search("rust"), &[0, 2]);
+        assert_eq!(index.search("RUST"), &[0, 2]);
+        // Usernames are part of the summary too
+        assert_eq!(index.search("ferris"), &[2]);
+        assert!(index.search("hockey").is_empty());
+    }
+
+    #[test]
+    fn index_mixes_summary_types() {
+        let mut index = Index::new();
+        assert_eq!(index.add(&tweet("iceburgh", "Go Penguins!")), 0);
+        assert_eq!(
+            index.add(&article("Penguins win", "Iceburgh", "Pittsburgh")),
+            1
+        );
+
+        assert_eq!(index.search("penguins"), &[0, 1]);
+        assert_eq!(index.search("pittsburgh"), &[1]);
+    }
+
+    #[test]
+    fn table_pads_columns_to_widest_cell() {
+        let rows = vec![vec!["name", "language"], vec!["Ferris", "Rust"]];
+
+        assert_eq!(print_table(&rows), "name   | language\nFerris | Rust");
+    }
+
+    #[test]
+    fn table_of_numbers_and_uneven_rows() {
+        let rows = vec![vec![1, 200, 3], vec![40, 5], vec![]];
+
+        assert_eq!(print_table(&rows), "1  | 200 | 3\n40 | 5\n");
+    }
+
+    #[test]
+    fn table_counts_chars_not_bytes() {
+        // 'é' is 2 bytes, but only takes up one column
+        let rows = vec![vec!["café", "x"], vec!["tea", "y"]];
+
+        assert_eq!(print_table(&rows), "café | x\ntea  | y");
+        assert_eq!(print_table::<String>(&[]), "");
+    }
+}
@@ -2,7 +2,7 @@
 // Code blocks are not necessarily in order from the book
 
 // We need to import the trait along with the struct to use methods defined by the traits
-use traits::{NewsArticle, Summary, Tweet};
+use traits::{print_table, Index, NewsArticle, Summary, Tweet};
 
 fn main() {
     let tweet = Tweet {
@@ -25,4 +25,17 @@ fn main() {
     };
 
     println!("New article available! {}", article.summarize());
+
+    // Note: not in book
+    let mut index = Index::new();
+    index.add(&tweet);
+    index.add(&article);
+    println!("Items mentioning penguins: {:?}", index.search("penguins"));
+
+    // Note: not in book
+    let rows = vec![
+        vec![String::from("username"), tweet.username.clone()],
+        vec![String::from("author"), article.author.clone()],
+    ];
+    println!("{}", print_table(&rows));
 }
@@ -46,6 +46,111 @@ impl Inventory {
     }
 }
 
+//
+// Memoization with a Cacher
+//
+
+// Note: not in book (this example is from an earlier edition of the book)
+// If an expensive closure is needed in more than one place, we can store the closure and its result in a struct
+// The struct only calls the closure the first time the value is needed, and returns the saved (cached) result after that
+// This pattern is known as memoization or lazy evaluation
+
+// Cacher is generic over T, and the trait bound says that T is a closure that takes a u32 and returns a u32
+// Each closure has its own unique type, so we need generics to store one in a struct
+struct Cacher<T>
+where
+    T: Fn(u32) -> u32,
+{
+    calculation: T,
+    value: Option<u32>,
+}
+
+impl<T> Cacher<T>
+where
+    T: Fn(u32) -> u32,
+{
+    fn new(calculation: T) -> Cacher<T> {
+        Cacher {
+            calculation,
+            value: None,
+        }
+    }
+
+    // Note that the first `arg` is cached, and later calls with a different `arg` will still return the first result
+    fn value(&mut self, arg: u32) -> u32 {
+        match self.value {
+            Some(v) => v,
+            None => {
+                let v = (self.calculation)(arg);
+                self.value = Some(v);
+                v
+            }
+        }
+    }
+}
+
+fn simulated_expensive_calculation(intensity: u32) -> u32 {
+    println!("calculating slowly...");
+    thread::sleep(Duration::from_secs(2));
+    intensity
+}
+
+fn generate_workout(intensity: u32, random: u32) {
+    for line in workout_plan(intensity, random, simulated_expensive_calculation) {
+        println!("{line}");
+    }
+}
+
+// The workout logic takes the calculation as an argument, so tests can pass in a closure that counts how many times it was called
+// A function like simulated_expensive_calculation() can be passed in too, since functions implement the `Fn` traits
+fn workout_plan(intensity: u32, random: u32, calculation: impl Fn(u32) -> u32) -> Vec<String> {
+    let mut expensive_result = Cacher::new(calculation);
+
+    if intensity < 25 {
+        // The expensive value is needed twice here, but the calculation only runs once
+        vec![
+            format!("Today, do {} pushups!", expensive_result.value(intensity)),
+            format!("Next, do {} situps!", expensive_result.value(intensity)),
+        ]
+    } else if random == 3 {
+        // The calculation doesn't run at all in this branch
+        vec![String::from(
+            "Take a break today! Remember to stay hydrated!",
+        )]
+    } else {
+        vec![format!(
+            "Today, run for {} minutes!",
+            expensive_result.value(intensity)
+        )]
+    }
+}
+
+//
+// Accepting `FnMut` Closures
+//
+
+// Note: not in book
+// apply_n() calls `f` n times, so it can't accept an `FnOnce` closure, which might only be callable once
+// Using `FnMut` instead of `Fn` as the trait bound lets callers pass closures that mutate their captured values (e.g. a counter)
+// `f` needs to be declared as `mut`, since calling an `FnMut` closure needs a mutable borrow of the closure
+fn apply_n<F: FnMut()>(mut f: F, n: usize) {
+    for _ in 0..n {
+        f();
+    }
+}
+
+//
+// Returning Closures that Capture References
+//
+
+// Note: not in book
+// Like make_a_cloner_v2() at the end of main(), the returned closure captures `prefix`, which is a reference
+// `+ '_` ties the closure to the lifetime of `prefix`, so the closure can't be used after the string behind `prefix` is dropped
+// The `&str` argument of the closure has its own lifetime, and only needs to live for the duration of each call
+pub fn make_appender(prefix: &str) -> impl Fn(&str) -> String + '_ {
+    move |arg| format!("{prefix}{arg}")
+}
+
 fn main() {
     let store = Inventory {
         shirts: vec![ShirtColor::Blue, ShirtColor::Red, ShirtColor::Blue],
@@ -269,6 +374,12 @@ fn main() {
     });
     println!("{:#?}, sorted in {num_sort_operations} operations", list);
 
+    // Note: not in book
+    // Our own functions can accept `FnMut` closures too
+    let mut greetings = vec![];
+    apply_n(|| greetings.push("hello"), 3);
+    println!("{:?}", greetings);
+
     //
     // Closures Must Name Captured Lifetimes
     //
@@ -313,4 +424,126 @@ fn main() {
     }
     // We can replace the `'a` generic lifetime over the whole function with the `'_` indicator on the return type,
     // to show that the returned closure depends on some lifetime
+
+    // Note: not in book
+    // make_appender() (defined outside of main()) returns a closure that borrows `greeting`
+    let greeting = String::from("Hello, ");
+    let greet = make_appender(&greeting);
+    println!("{}", greet("world"));
+    // Won't work, since `greet` still borrows `greeting`
+    // drop(greeting);
+    println!("{}", greet("Ferris"));
+
+    //
+    // Memoization with a Cacher
+    //
+
+    // Note: not in book
+    // The "calculating slowly..." message is only printed once, even though both exercises use the expensive value
+    generate_workout(10, 7);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    // The closures need to implement `Fn`, so the call count is kept in a Cell, which can be updated through an immutable reference
+    #[test]
+    fn low_intensity_calculates_once() {
+        let calls = Cell::new(0);
+        let plan = workout_plan(10, 7, |num| {
+            calls.set(calls.get() + 1);
+            num
+        });
+
+        assert_eq!(plan, vec!["Today, do 10 pushups!", "Next, do 10 situps!"]);
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn high_intensity_calculates_once() {
+        let calls = Cell::new(0);
+        let plan = workout_plan(30, 7, |num| {
+            calls.set(calls.get() + 1);
+            num
+        });
+
+        assert_eq!(plan, vec!["Today, run for 30 minutes!"]);
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn break_day_never_calculates() {
+        let calls = Cell::new(0);
+        let plan = workout_plan(30, 3, |num| {
+            calls.set(calls.get() + 1);
+            num
+        });
+
+        assert_eq!(plan, vec!["Take a break today! Remember to stay hydrated!"]);
+        assert_eq!(calls.get(), 0);
+    }
+
+    #[test]
+    fn apply_n_zero_times() {
+        let mut count_calls = 0;
+        apply_n(|| count_calls += 1, 0);
+
+        assert_eq!(count_calls, 0);
+    }
+
+    #[test]
+    fn apply_n_three_times() {
+        let mut count_calls = 0;
+        apply_n(|| count_calls += 1, 3);
+
+        // The closure's mutable borrow of count_calls ends once apply_n() returns, so we can read it again
+        assert_eq!(count_calls, 3);
+    }
+
+    #[test]
+    fn apply_n_mutates_captured_state() {
+        let mut list = vec![1];
+        apply_n(
+            || {
+                let last = *list.last().unwrap();
+                list.push(last * 2);
+            },
+            4,
+        );
+
+        assert_eq!(list, vec![1, 2, 4, 8, 16]);
+    }
+
+    #[test]
+    fn make_appender_prepends_prefix() {
+        let append = make_appender("> ");
+
+        assert_eq!(append("one"), "> one");
+        assert_eq!(append("two"), "> two");
+        assert_eq!(append(""), "> ");
+    }
+
+    #[test]
+    fn make_appender_borrows_prefix() {
+        let mut prefix = String::from("v1: ");
+        {
+            let append = make_appender(&prefix);
+            let arg = String::from("release");
+            assert_eq!(append(&arg), "v1: release");
+        }
+        // Once the closure is out of scope, the prefix is no longer borrowed and can be changed
+        prefix.replace_range(..2, "v2");
+        let append = make_appender(&prefix);
+        assert_eq!(append("release"), "v2: release");
+    }
+
+    #[test]
+    fn cacher_keeps_first_value() {
+        let mut c = Cacher::new(|a| a * 2);
+
+        assert_eq!(c.value(1), 2);
+        assert_eq!(c.value(5), 2);
+    }
 }
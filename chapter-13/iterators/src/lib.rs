@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
 //
 // The `Iterator` Trait and the `next` method
 //
@@ -50,6 +53,29 @@ fn iterator_sum() {
     assert_eq!(total, 6);
 }
 
+// Note: not in book
+// sum() can add up a different type than the items being iterated over, as long as that type implements the Sum trait
+// Here, map() widens each i32 into an i64 before squaring, so squaring a large i32 (or adding up many squares) won't overflow
+// Squaring first and then converting (i.e. `(x * x) as i64`) would overflow in the i32 multiplication
+pub fn sum_of_squares(v: &[i32]) -> i64 {
+    v.iter().map(|&x| (x as i64) * (x as i64)).sum()
+}
+
+#[test]
+fn sum_of_squares_small_values() {
+    assert_eq!(sum_of_squares(&[1, 2, 3]), 14);
+    assert_eq!(sum_of_squares(&[-4]), 16);
+    assert_eq!(sum_of_squares(&[]), 0);
+}
+
+#[test]
+fn sum_of_squares_wider_than_i32() {
+    // 50_000 * 50_000 = 2_500_000_000, which is larger than i32::MAX (2_147_483_647)
+    assert_eq!(sum_of_squares(&[50_000]), 2_500_000_000);
+    let max = i32::MAX as i64;
+    assert_eq!(sum_of_squares(&[i32::MAX, i32::MAX]), 2 * max * max);
+}
+
 //
 // Using Closures that Capture Their Environment
 //
@@ -62,10 +88,11 @@ fn iterator_sum() {
 // If the closure returns `true`, the value will be included in the iterator produced by filter(),
 // If the closure returns `false`, the value will not be included
 
+// Note: not in book - Shoe is pub so that it can be passed to style_names_in_size() from outside this library
 #[derive(PartialEq, Debug)]
-struct Shoe {
-    size: u32,
-    style: String,
+pub struct Shoe {
+    pub size: u32,
+    pub style: String,
 }
 
 // Note: shoes_in_size takes ownership of the `shoes` vector
@@ -75,6 +102,159 @@ fn shoes_in_size(shoes: Vec<Shoe>, shoe_size: u32) -> Vec<Shoe> {
     shoes.into_iter().filter(|s| s.size == shoe_size).collect()
 }
 
+// Note: not in book
+// Multiple adaptors can be chained together before the consuming adaptor at the end
+// style_names_in_size() only borrows the shoes, so iter() gives us references (&Shoe) instead of owned values
+// filter() receives a &&Shoe (a reference to the item), and map() clones the style so the returned Vec owns its Strings
+// Nothing happens until collect() is called, and each shoe goes through filter() then map() one at a time, so the order is preserved
+pub fn style_names_in_size(shoes: &[Shoe], size: u32) -> Vec<String> {
+    shoes
+        .iter()
+        .filter(|s| s.size == size)
+        .map(|s| s.style.clone())
+        .collect()
+}
+
+//
+// Grouping Items by a Key
+//
+
+// Note: not in book
+// shoes_in_size() keeps the shoes of one size, group_by_key() instead sorts every item into a group, e.g. a group for each shoe size
+// `key` works out which group an item goes in, and it only needs a reference to the item, since the item itself is moved into the group afterwards
+// The items are visited in order and pushed onto the end of their group, so each group keeps the order the items were in
+// Only the groups themselves are in no particular order, since they are stored in a HashMap
+pub fn group_by_key<T, K: Eq + Hash>(items: Vec<T>, key: impl Fn(&T) -> K) -> HashMap<K, Vec<T>> {
+    let mut groups: HashMap<K, Vec<T>> = HashMap::new();
+    for item in items {
+        groups.entry(key(&item)).or_default().push(item);
+    }
+    groups
+}
+
+#[test]
+fn group_numbers_by_parity() {
+    let groups = group_by_key(vec![1, 2, 3, 4, 5, 6, 7], |n| n % 2 == 0);
+
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[&true], vec![2, 4, 6]);
+    assert_eq!(groups[&false], vec![1, 3, 5, 7]);
+}
+
+#[test]
+fn group_strings_by_first_letter() {
+    let words = vec![
+        "apple",
+        "banana",
+        "avocado",
+        "blueberry",
+        "cherry",
+        "apricot",
+    ];
+
+    // An empty string doesn't have a first letter, so the key is an Option<char>
+    let groups = group_by_key(words, |word| word.chars().next());
+
+    assert_eq!(groups[&Some('a')], vec!["apple", "avocado", "apricot"]);
+    assert_eq!(groups[&Some('b')], vec!["banana", "blueberry"]);
+    assert_eq!(groups[&Some('c')], vec!["cherry"]);
+    assert_eq!(groups.get(&Some('d')), None);
+}
+
+#[test]
+fn group_nothing() {
+    assert!(group_by_key(Vec::<i32>::new(), |n| *n).is_empty());
+}
+
+//
+// Looking Ahead with peekable()
+//
+
+// Note: not in book
+// The peekable() adaptor wraps an iterator so that we can look at the next item with peek() without consuming it
+// Example: run-length encoding, which replaces a run of the same character with the character and the length of the run (e.g. "aaabb" -> a3 b2)
+// chars() iterates over Unicode scalar values rather than bytes, so multi-byte characters like 'é' are counted once each
+pub fn rle(input: &str) -> Vec<(char, usize)> {
+    let mut runs = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    // next() starts a new run, and next_if_eq() only consumes the following character if it continues the run
+    while let Some(c) = chars.next() {
+        let mut count = 1;
+        while chars.next_if_eq(&c).is_some() {
+            count += 1;
+        }
+        runs.push((c, count));
+    }
+    runs
+}
+
+#[test]
+fn rle_runs() {
+    assert_eq!(rle("aaabb"), vec![('a', 3), ('b', 2)]);
+    // The same character can start a new run later on
+    assert_eq!(rle("aabaa"), vec![('a', 2), ('b', 1), ('a', 2)]);
+}
+
+#[test]
+fn rle_single_chars() {
+    assert_eq!(rle("abc"), vec![('a', 1), ('b', 1), ('c', 1)]);
+    assert_eq!(rle("z"), vec![('z', 1)]);
+}
+
+#[test]
+fn rle_empty() {
+    assert_eq!(rle(""), vec![]);
+}
+
+#[test]
+fn rle_multi_byte_chars() {
+    // 'é' is 2 bytes and '🦀' is 4 bytes in UTF-8, but each is a single char
+    assert_eq!(rle("éé🦀🦀🦀"), vec![('é', 2), ('🦀', 3)]);
+}
+
+//
+// Iterating over Windows of a Slice
+//
+
+// Note: not in book
+// Slices have a windows() method that returns an iterator over every overlapping sub-slice of a given length
+// e.g. [1.0, 2.0, 3.0].windows(2) produces [1.0, 2.0] and then [2.0, 3.0]
+// This is useful for smoothing time-series data with a moving average
+// If the window is larger than the data, windows() doesn't produce anything, so the result is an empty vector
+// A window of 0 doesn't have an average, so moving_average() panics (windows() itself also panics on a size of 0)
+pub fn moving_average(data: &[f64], window: usize) -> Vec<f64> {
+    if window == 0 {
+        panic!("moving_average window must be greater than 0");
+    }
+
+    data.windows(window)
+        .map(|w| w.iter().sum::<f64>() / window as f64)
+        .collect()
+}
+
+#[test]
+fn moving_average_of_series() {
+    let data = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+    assert_eq!(moving_average(&data, 3), vec![2.0, 3.0, 4.0, 5.0]);
+    // A window of 1 returns the data unchanged
+    assert_eq!(moving_average(&data, 1), data.to_vec());
+    // A window of the same length as the data has a single average
+    assert_eq!(moving_average(&data, 6), vec![3.5]);
+}
+
+#[test]
+fn moving_average_window_larger_than_data() {
+    assert_eq!(moving_average(&[1.0, 2.0], 3), vec![]);
+    assert_eq!(moving_average(&[], 1), vec![]);
+}
+
+#[test]
+#[should_panic(expected = "window must be greater than 0")]
+fn moving_average_rejects_zero_window() {
+    moving_average(&[1.0, 2.0], 0);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -112,4 +292,32 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn style_names_by_size() {
+        let shoes = vec![
+            Shoe {
+                size: 10,
+                style: String::from("sneaker"),
+            },
+            Shoe {
+                size: 13,
+                style: String::from("sandal"),
+            },
+            Shoe {
+                size: 10,
+                style: String::from("boot"),
+            },
+            Shoe {
+                size: 10,
+                style: String::from("loafer"),
+            },
+        ];
+
+        let styles = style_names_in_size(&shoes, 10);
+
+        assert_eq!(styles, vec!["sneaker", "boot", "loafer"]);
+        // The shoes were only borrowed, so they can still be used
+        assert_eq!(shoes.len(), 4);
+    }
 }
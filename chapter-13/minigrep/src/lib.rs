@@ -1,11 +1,49 @@
 use std::env;
 use std::error::Error;
-use std::fs;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::sync::Mutex;
+use std::thread;
 
 pub struct Config {
     pub query: String,
-    pub file_path: String,
+    // Patterns loaded from --pattern-file, a line matches if it contains any of them (like `grep -f`)
+    // When there are patterns, `query` is empty and isn't used
+    pub patterns: Vec<String>,
+    // One or more files to search
+    pub file_paths: Vec<String>,
     pub ignore_case: bool,
+    // Print the lines that don't match the query instead (like `grep -v`)
+    pub invert: bool,
+    // Print the number of matching lines instead of the lines themselves (like `grep -c`)
+    pub count: bool,
+    // The number of threads used to search the files, 1 searches the files one after another
+    pub jobs: usize,
+    // Print the line number and column of each match (for jumping to the match in an editor)
+    pub column: bool,
+    // Print the byte position of the start of each matching line in its file (like `grep -b`)
+    pub byte_offset: bool,
+    // End each line of output with a NUL byte ('\0') instead of a newline, for tools like `xargs -0`
+    // A file name can contain a newline, but it can't contain a NUL byte, so this keeps each result in one piece
+    pub null: bool,
+    // Print nothing, only report whether anything matched through run()'s return value (like `grep -q`)
+    pub quiet: bool,
+    // Print what the arguments were parsed into (see describe()), without searching anything
+    pub dry_run: bool,
+}
+
+// One matching line, along with where it was found
+// The search functions return borrowed lines, a Match owns everything, so it can be kept after `contents` is gone
+// Each output format (plain lines, --column, or a format like JSON) can be written from the same list of Matches
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match {
+    // The file the line came from
+    pub path: String,
+    // 1-based, like the line numbers in an editor
+    pub line_number: usize,
+    // 1-based, counted in characters rather than bytes (see search_with_columns())
+    pub column: usize,
+    pub text: String,
 }
 
 impl Config {
@@ -15,43 +53,441 @@ impl Config {
         // Skip over the first arg, which is the name of the program
         args.next();
 
-        let query = match args.next() {
-            Some(arg) => arg,
-            None => return Err("Didn't get a query string"),
+        // Check if the IGNORE_CASE enviroment variable is set
+        // Note that env::var() returns a result, but we don't care about the value in Ok() (i.e. the value of the enviroment variable)
+        let ignore_case = env::var("IGNORE_CASE").is_ok();
+
+        let mut builder = ConfigBuilder::new().ignore_case(ignore_case);
+
+        // The args that aren't flags are the query followed by the files to search
+        // With --pattern-file, the patterns come from the file instead, so every one of these args is a file
+        // The flags can go anywhere, so the query can't be picked out until all of the args have been seen
+        let mut positional = Vec::new();
+        let mut pattern_file = None;
+
+        // We use `while let` instead of a for loop, since `--jobs` needs to take the next arg from the iterator as its value
+        while let Some(arg) = args.next() {
+            builder = match arg.as_str() {
+                "-v" | "--invert" => builder.invert(true),
+                "-c" | "--count" => builder.count(true),
+                "--column" => builder.column(true),
+                "-b" | "--byte-offset" => builder.byte_offset(true),
+                "-Z" | "--null" => builder.null(true),
+                "-q" | "--quiet" => builder.quiet(true),
+                "--dry-run" => builder.dry_run(true),
+                "-j" | "--jobs" => match args.next().and_then(|n| n.parse().ok()) {
+                    Some(jobs) => builder.jobs(jobs),
+                    None => return Err("--jobs needs a number"),
+                },
+                "-f" | "--pattern-file" => match args.next() {
+                    Some(path) => {
+                        pattern_file = Some(path);
+                        builder
+                    }
+                    None => return Err("--pattern-file needs a path"),
+                },
+                // Everything after `--` is a query or a file, even if it starts with '-' (like grep)
+                // e.g. `minigrep -- -1 notes.txt` searches for "-1"
+                "--" => {
+                    positional.extend(args.by_ref());
+                    builder
+                }
+                // A query or file starting with '-' needs to come after `--`, so typos in flags are still caught here
+                flag if flag.starts_with('-') => return Err("Unknown flag"),
+                _ => {
+                    positional.push(arg);
+                    builder
+                }
+            };
+        }
+
+        let mut positional = positional.into_iter();
+
+        builder = match pattern_file {
+            Some(path) => {
+                // The error type is a &'static str, so the details of the io::Error can't be passed on
+                let file = File::open(path).map_err(|_| "Couldn't open the pattern file")?;
+                let patterns = read_patterns(BufReader::new(file))
+                    .map_err(|_| "Couldn't read the pattern file")?;
+                builder.patterns(patterns)
+            }
+            None => match positional.next() {
+                Some(query) => builder.query(&query),
+                None => return Err("Didn't get a query string"),
+            },
+        };
+
+        // At least one file is needed
+        match positional.next() {
+            Some(path) => builder = builder.path(&path),
+            None => return Err("Didn't get a file path"),
+        }
+        for path in positional {
+            builder = builder.path(&path);
+        }
+
+        builder.build()
+    }
+
+    // Returns a summary of what is going to be searched for, where, and which flags are on, for checking how the arguments were parsed
+    // e.g. for `minigrep -c -j 2 to poem.txt notes.txt`:
+    // query: "to"
+    // files: poem.txt, notes.txt
+    // flags: --count --jobs 2
+    // The query is printed with {:?}, so quotes and any leading or trailing spaces can be seen
+    pub fn describe(&self) -> String {
+        let searching_for = if self.patterns.is_empty() {
+            format!("query: {:?}", self.query)
+        } else {
+            format!("patterns: {:?}", self.patterns)
         };
 
-        let file_path = match args.next() {
-            Some(arg) => arg,
-            None => return Err("Didn't get a query string"),
+        let mut flags = Vec::new();
+        // ignore_case is set by the IGNORE_CASE environment variable rather than a flag
+        if self.ignore_case {
+            flags.push(String::from("IGNORE_CASE"));
+        }
+        if self.invert {
+            flags.push(String::from("--invert"));
+        }
+        if self.count {
+            flags.push(String::from("--count"));
+        }
+        // 1 job is the default, so it's only a flag if there is more than one
+        if self.jobs > 1 {
+            flags.push(format!("--jobs {}", self.jobs));
+        }
+        if self.column {
+            flags.push(String::from("--column"));
+        }
+        if self.byte_offset {
+            flags.push(String::from("--byte-offset"));
+        }
+        if self.null {
+            flags.push(String::from("--null"));
+        }
+        if self.quiet {
+            flags.push(String::from("--quiet"));
+        }
+        if self.dry_run {
+            flags.push(String::from("--dry-run"));
+        }
+        let flags = if flags.is_empty() {
+            String::from("none")
+        } else {
+            flags.join(" ")
         };
 
-        // Check if the IGNORE_CASE enviroment variable is set
-        // Note that env::var() returns a result, but we don't care about the value in Ok() (i.e. the value of the enviroment variable)
-        let ignore_case = env::var("IGNORE_CASE").is_ok();
+        format!(
+            "{searching_for}\nfiles: {}\nflags: {flags}",
+            self.file_paths.join(", ")
+        )
+    }
+}
+
+// ConfigBuilder lets a Config be put together one setting at a time, without needing to parse command line arguments
+// Each setter takes ownership of the builder and returns it, so the calls can be chained:
+// ConfigBuilder::new().query("to").path("poem.txt").ignore_case(true).build()
+// Settings that aren't set keep their default values (an empty query, no files, false for the flags and 1 job)
+pub struct ConfigBuilder {
+    query: String,
+    patterns: Vec<String>,
+    file_paths: Vec<String>,
+    ignore_case: bool,
+    invert: bool,
+    count: bool,
+    jobs: usize,
+    column: bool,
+    byte_offset: bool,
+    null: bool,
+    quiet: bool,
+    dry_run: bool,
+}
+
+impl ConfigBuilder {
+    pub fn new() -> ConfigBuilder {
+        ConfigBuilder {
+            query: String::new(),
+            patterns: Vec::new(),
+            file_paths: Vec::new(),
+            ignore_case: false,
+            invert: false,
+            count: false,
+            jobs: 1,
+            column: false,
+            byte_offset: false,
+            null: false,
+            quiet: false,
+            dry_run: false,
+        }
+    }
+
+    pub fn query(mut self, query: &str) -> ConfigBuilder {
+        self.query = query.to_string();
+        self
+    }
+
+    // Replaces the query with a list of patterns, see read_patterns()
+    pub fn patterns(mut self, patterns: Vec<String>) -> ConfigBuilder {
+        self.patterns = patterns;
+        self
+    }
+
+    // Adds a file to search, so path() can be called once for each file
+    pub fn path(mut self, file_path: &str) -> ConfigBuilder {
+        self.file_paths.push(file_path.to_string());
+        self
+    }
+
+    pub fn ignore_case(mut self, ignore_case: bool) -> ConfigBuilder {
+        self.ignore_case = ignore_case;
+        self
+    }
+
+    pub fn invert(mut self, invert: bool) -> ConfigBuilder {
+        self.invert = invert;
+        self
+    }
+
+    pub fn count(mut self, count: bool) -> ConfigBuilder {
+        self.count = count;
+        self
+    }
+
+    pub fn jobs(mut self, jobs: usize) -> ConfigBuilder {
+        self.jobs = jobs;
+        self
+    }
+
+    pub fn column(mut self, column: bool) -> ConfigBuilder {
+        self.column = column;
+        self
+    }
+
+    pub fn byte_offset(mut self, byte_offset: bool) -> ConfigBuilder {
+        self.byte_offset = byte_offset;
+        self
+    }
+
+    pub fn null(mut self, null: bool) -> ConfigBuilder {
+        self.null = null;
+        self
+    }
+
+    pub fn quiet(mut self, quiet: bool) -> ConfigBuilder {
+        self.quiet = quiet;
+        self
+    }
+
+    pub fn dry_run(mut self, dry_run: bool) -> ConfigBuilder {
+        self.dry_run = dry_run;
+        self
+    }
+
+    // An empty query would match every line, which is almost certainly a mistake
+    pub fn build(self) -> Result<Config, &'static str> {
+        if self.query.is_empty() && self.patterns.is_empty() {
+            return Err("Query string can't be empty");
+        }
+        // There would be no way to tell which of the two should be searched for
+        if !self.query.is_empty() && !self.patterns.is_empty() {
+            return Err("A query string can't be used with --pattern-file");
+        }
+        // search_with_columns() only looks for a single query
+        if self.column && !self.patterns.is_empty() {
+            return Err("--column can't be used with --pattern-file");
+        }
+        // With 0 threads, no files would ever be searched
+        if self.jobs == 0 {
+            return Err("--jobs needs to be at least 1");
+        }
+        // Inverted results don't contain the query, so there is no column to report
+        if self.column && self.invert {
+            return Err("--column can't be used with --invert");
+        }
+        // Both change the start of each line, so only one of them can be used
+        if self.column && self.byte_offset {
+            return Err("--column can't be used with --byte-offset");
+        }
 
         Ok(Config {
-            query,
-            file_path,
-            ignore_case,
+            query: self.query,
+            patterns: self.patterns,
+            file_paths: self.file_paths,
+            ignore_case: self.ignore_case,
+            invert: self.invert,
+            count: self.count,
+            jobs: self.jobs,
+            column: self.column,
+            byte_offset: self.byte_offset,
+            null: self.null,
+            quiet: self.quiet,
+            dry_run: self.dry_run,
         })
     }
 }
 
+impl Default for ConfigBuilder {
+    fn default() -> Self {
+        ConfigBuilder::new()
+    }
+}
+
 // The Box<dyn Error> means return an object that implements Error
-pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(config.file_path)?;
+// run() prints the results to stdout, see run_with_writer() to send them somewhere else
+// Returns whether any line matched (or with --invert, whether any line didn't match), so main() can pick the exit code
+pub fn run(config: Config) -> Result<bool, Box<dyn Error>> {
+    run_with_writer(config, &mut io::stdout())
+}
+
+// Like run(), but the results are written to `out` instead of stdout
+// Any type that implements Write can be passed in, e.g. a file, or a Vec<u8> to capture the output in a test
+pub fn run_with_writer(config: Config, out: &mut dyn Write) -> Result<bool, Box<dyn Error>> {
+    if config.jobs > 1 {
+        // The files are opened here so that a missing file is reported before any searching starts
+        // Reading the contents (the slow part for large files) happens in the worker threads
+        let mut sources = Vec::new();
+        for path in &config.file_paths {
+            sources.push((path.clone(), File::open(path)?));
+        }
+        // The worker threads share their output through a Mutex, which needs a type that can be sent between threads
+        // `out` might not be (e.g. it could be holding an Rc), so the results are collected in a buffer and written to `out` once all of the threads are done
+        let buffer = Mutex::new(Vec::new());
+        let matched = search_parallel(&config, sources, &buffer)?;
+        out.write_all(&buffer.into_inner().unwrap())?;
+        return Ok(matched);
+    }
+
+    let mut matched = false;
+    for path in &config.file_paths {
+        let contents = fs::read_to_string(path)?;
+        // write_results() is called before the `||`, so every file is still searched after the first match
+        matched = write_results(&config, path, &contents, out)? || matched;
+    }
+
+    Ok(matched)
+}
+
+// Writes the results of searching one file to `out`, and returns whether there were any results
+// When more than one file is searched, each line is prefixed with the file it came from (like grep does)
+fn write_results(
+    config: &Config,
+    path: &str,
+    contents: &str,
+    out: &mut (impl Write + ?Sized),
+) -> io::Result<bool> {
+    let results = if !config.patterns.is_empty() {
+        if config.invert {
+            contents
+                .lines()
+                .filter(|line| !contains_any(line, &config.patterns, config.ignore_case))
+                .collect()
+        } else {
+            search_any(&config.patterns, contents, config.ignore_case)
+        }
+    } else if config.invert {
+        search_inverted(&config.query, contents, config.ignore_case)
+    } else if config.ignore_case {
+        search_case_insensitive(&config.query, contents)
+    } else {
+        search(&config.query, contents)
+    };
+    let matched = !results.is_empty();
+
+    if config.quiet {
+        return Ok(matched);
+    }
 
-    let results = if config.ignore_case {
-        search_case_insensitive(&config.query, &contents)
+    let prefix = if config.file_paths.len() > 1 {
+        format!("{path}:")
     } else {
-        search(&config.query, &contents)
+        String::new()
     };
+    // writeln!() always ends with "\n", so write!() is used with the separator added on the end instead
+    let end = if config.null { '\0' } else { '\n' };
+
+    if config.count {
+        write!(out, "{prefix}{}{end}", results.len())?;
+        return Ok(matched);
+    }
+
+    if config.column {
+        for m in search_rich(&config.query, path, contents, config.ignore_case) {
+            write!(
+                out,
+                "{prefix}{}:{}: {}{end}",
+                m.line_number, m.column, m.text
+            )?;
+        }
+        return Ok(matched);
+    }
 
     for line in results {
-        println!("{line}");
+        if config.byte_offset {
+            write!(out, "{prefix}{}:{line}{end}", byte_offset(contents, line))?;
+        } else {
+            write!(out, "{prefix}{line}{end}")?;
+        }
     }
 
-    Ok(())
+    Ok(matched)
+}
+
+// Returns the byte position in `contents` where `line` starts
+// Every line returned by the search functions is a slice of `contents`, so it points somewhere inside the same block of memory
+// The difference between the two addresses is the number of bytes before the line, including every "\n" (or "\r\n") that lines() removed
+// This only works for slices of `contents`, a copy of a line would be somewhere else in memory
+fn byte_offset(contents: &str, line: &str) -> usize {
+    line.as_ptr() as usize - contents.as_ptr() as usize
+}
+
+// Searches each (name, reader) source using `config.jobs` worker threads, and writes the results to `out`
+// Returns whether any of the sources had results
+// The workers share a queue of sources behind a Mutex, and each worker takes the next source from the queue when it is done with the last one
+// A worker writes a file's results into its own buffer first, and then locks `out` to write the whole buffer at once,
+// so the lines from one file are never mixed in with the lines from another file (but the files can finish in any order)
+// thread::scope() waits for all of the threads to finish before returning, which lets the threads borrow `config`, `queue` and `out`
+pub fn search_parallel<R, W>(
+    config: &Config,
+    sources: Vec<(String, R)>,
+    out: &Mutex<W>,
+) -> io::Result<bool>
+where
+    R: Read + Send,
+    W: Write + Send,
+{
+    let queue = Mutex::new(sources.into_iter());
+
+    thread::scope(|s| {
+        let mut handles = Vec::new();
+
+        for _ in 0..config.jobs {
+            handles.push(s.spawn(|| -> io::Result<bool> {
+                let mut matched = false;
+                loop {
+                    // The lock on the queue is released at the end of this statement, so other workers can take sources while this one searches
+                    let next = queue.lock().unwrap().next();
+                    let Some((path, mut reader)) = next else {
+                        return Ok(matched);
+                    };
+
+                    let mut contents = String::new();
+                    reader.read_to_string(&mut contents)?;
+
+                    let mut buffer = Vec::new();
+                    matched = write_results(config, &path, &contents, &mut buffer)? || matched;
+                    out.lock().unwrap().write_all(&buffer)?;
+                }
+            }));
+        }
+
+        // Return the first error from any of the workers
+        let mut matched = false;
+        for handle in handles {
+            matched = handle.join().unwrap()? || matched;
+        }
+        Ok(matched)
+    })
 }
 
 // The returned vector will live as long as the data passed into contents
@@ -71,6 +507,153 @@ pub fn search_case_insensitive<'a>(query: &str, contents: &'a str) -> Vec<&'a st
         .collect()
 }
 
+// Like search(), but the matching lines are copied into owned Strings
+// The lines returned by search() borrow from `contents`, so they can't outlive it or be moved into another thread
+// Owned Strings don't borrow anything, so they can be sent over a channel (e.g. from a thread that reads and searches a file)
+pub fn search_owned(query: &str, contents: &str) -> Vec<String> {
+    search(query, contents)
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+// Reads one pattern per line (e.g. from a --pattern-file), for use with search_any()
+// Blank lines are skipped, since an empty pattern would match every line
+// Spaces are kept, so a pattern can start or end with a space
+pub fn read_patterns<R: BufRead>(reader: R) -> io::Result<Vec<String>> {
+    let mut patterns = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            patterns.push(line);
+        }
+    }
+    Ok(patterns)
+}
+
+// Returns the lines that contain at least one of the patterns
+pub fn search_any<'a>(patterns: &[String], contents: &'a str, ignore_case: bool) -> Vec<&'a str> {
+    contents
+        .lines()
+        .filter(|line| contains_any(line, patterns, ignore_case))
+        .collect()
+}
+
+// any() stops at the first pattern that matches, so the rest of the patterns aren't checked
+fn contains_any(line: &str, patterns: &[String], ignore_case: bool) -> bool {
+    if ignore_case {
+        let line = line.to_lowercase();
+        patterns
+            .iter()
+            .any(|pattern| line.contains(&pattern.to_lowercase()))
+    } else {
+        patterns
+            .iter()
+            .any(|pattern| line.contains(pattern.as_str()))
+    }
+}
+
+// Like search(), but reads from anything that implements BufRead (a file, stdin, a network stream, etc.) instead of a &str
+// The lines are read one at a time, so the returned lines need to be owned Strings rather than slices of `contents`
+// Reading a line can fail (e.g. invalid UTF-8), so the error is passed back to the caller
+pub fn search_reader<R: BufRead>(query: &str, reader: R) -> io::Result<Vec<String>> {
+    let mut results = Vec::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        if line.contains(query) {
+            results.push(line);
+        }
+    }
+
+    Ok(results)
+}
+
+// Like search_reader(), but each matching line is written to `out` as soon as it is found instead of being collected into a Vec
+// Only one line is held in memory at a time, so this works for files that are too large to read in all at once
+// Returns the number of matching lines that were written
+pub fn search_streaming<R: BufRead, W: Write>(
+    query: &str,
+    reader: R,
+    out: &mut W,
+) -> io::Result<usize> {
+    let mut count = 0;
+
+    for line in reader.lines() {
+        let line = line?;
+        if line.contains(query) {
+            writeln!(out, "{line}")?;
+            count += 1;
+        }
+    }
+
+    Ok(count)
+}
+
+// Returns the 1-based line number, the 1-based column of the first occurrence of the query, and the text of each matching line
+// find() returns a byte index, but a character like 'é' takes up more than one byte,
+// so the column is found by counting the characters before that byte index instead
+// With ignore_case, the characters are counted in the original line, since lowercasing can change how many characters there are
+// e.g. 'İ' lowercases to "i̇", which is an 'i' followed by a combining dot (2 characters)
+pub fn search_with_columns<'a>(
+    query: &str,
+    contents: &'a str,
+    ignore_case: bool,
+) -> Vec<(usize, usize, &'a str)> {
+    let query_lowercase = query.to_lowercase();
+    contents
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let column = if ignore_case {
+                let line_lowercase = line.to_lowercase();
+                let index = line_lowercase.find(&query_lowercase)?;
+                // Count the characters of the original line that lowercase to something ending at or before `index`
+                let mut lowercase_end = 0;
+                line.chars()
+                    .take_while(|c| {
+                        lowercase_end += c.to_lowercase().map(char::len_utf8).sum::<usize>();
+                        lowercase_end <= index
+                    })
+                    .count()
+                    + 1
+            } else {
+                let index = line.find(query)?;
+                line[..index].chars().count() + 1
+            };
+            Some((i + 1, column, line))
+        })
+        .collect()
+}
+
+// Like search_with_columns(), but each result is a Match that also records which file it came from
+pub fn search_rich(query: &str, path: &str, contents: &str, ignore_case: bool) -> Vec<Match> {
+    search_with_columns(query, contents, ignore_case)
+        .into_iter()
+        .map(|(line_number, column, line)| Match {
+            path: path.to_string(),
+            line_number,
+            column,
+            text: line.to_string(),
+        })
+        .collect()
+}
+
+// Returns the lines that don't contain the query
+pub fn search_inverted<'a>(query: &str, contents: &'a str, ignore_case: bool) -> Vec<&'a str> {
+    let query_lowercase = query.to_lowercase();
+    contents
+        .lines()
+        .filter(|line| {
+            if ignore_case {
+                !line.to_lowercase().contains(&query_lowercase)
+            } else {
+                !line.contains(query)
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -110,4 +693,699 @@ Trust me.";
             search_case_insensitive(query, contents)
         );
     }
+
+    #[test]
+    fn inverted() {
+        let query = "rUsT";
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Trust me.";
+
+        assert_eq!(
+            vec![
+                "Rust:",
+                "safe, fast, productive.",
+                "Pick three.",
+                "Trust me."
+            ],
+            search_inverted(query, contents, false)
+        );
+        assert_eq!(
+            vec!["safe, fast, productive.", "Pick three."],
+            search_inverted(query, contents, true)
+        );
+    }
+
+    #[test]
+    fn builder_defaults() {
+        let config = ConfigBuilder::new().query("to").build().unwrap();
+
+        assert_eq!(config.query, "to");
+        assert!(config.file_paths.is_empty());
+        assert!(!config.ignore_case);
+        assert!(!config.invert);
+        assert!(!config.count);
+        assert_eq!(config.jobs, 1);
+    }
+
+    #[test]
+    fn builder_sets_every_flag() {
+        let config = ConfigBuilder::new()
+            .query("to")
+            .path("poem.txt")
+            .ignore_case(true)
+            .invert(true)
+            .count(true)
+            .jobs(4)
+            .build()
+            .unwrap();
+
+        assert_eq!(config.query, "to");
+        assert_eq!(config.file_paths, vec!["poem.txt"]);
+        assert!(config.ignore_case);
+        assert!(config.invert);
+        assert!(config.count);
+        assert_eq!(config.jobs, 4);
+    }
+
+    #[test]
+    fn builder_rejects_empty_query() {
+        let result = ConfigBuilder::new().path("poem.txt").build();
+
+        assert_eq!(result.err(), Some("Query string can't be empty"));
+    }
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter()
+            .map(|arg| arg.to_string())
+            .collect::<Vec<String>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn build_parses_flags() {
+        let config = Config::build(args(&["minigrep", "to", "poem.txt", "-v", "--count"])).unwrap();
+
+        assert_eq!(config.query, "to");
+        assert_eq!(config.file_paths, vec!["poem.txt"]);
+        assert!(config.invert);
+        assert!(config.count);
+    }
+
+    #[test]
+    fn build_parses_paths_and_jobs() {
+        let config = Config::build(args(&[
+            "minigrep", "to", "a.txt", "b.txt", "--jobs", "3", "c.txt",
+        ]))
+        .unwrap();
+
+        assert_eq!(config.file_paths, vec!["a.txt", "b.txt", "c.txt"]);
+        assert_eq!(config.jobs, 3);
+    }
+
+    #[test]
+    fn build_rejects_bad_jobs() {
+        let result = Config::build(args(&["minigrep", "to", "a.txt", "--jobs", "many"]));
+        assert_eq!(result.err(), Some("--jobs needs a number"));
+
+        let result = Config::build(args(&["minigrep", "to", "a.txt", "--jobs", "0"]));
+        assert_eq!(result.err(), Some("--jobs needs to be at least 1"));
+    }
+
+    #[test]
+    fn build_rejects_unknown_flag() {
+        let result = Config::build(args(&["minigrep", "to", "poem.txt", "--nope"]));
+
+        assert_eq!(result.err(), Some("Unknown flag"));
+    }
+
+    #[test]
+    fn owned_matches_borrowed() {
+        let query = "duct";
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Duct tape.
+Production ready.";
+
+        assert_eq!(search_owned(query, contents), search(query, contents));
+    }
+
+    #[test]
+    fn owned_results_can_be_sent_to_another_thread() {
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        thread::spawn(move || {
+            // `contents` is dropped at the end of the thread, but the results don't borrow from it
+            let contents = String::from("Rust:\nsafe, fast, productive.");
+            tx.send(search_owned("fast", &contents)).unwrap();
+        });
+
+        assert_eq!(rx.recv().unwrap(), vec!["safe, fast, productive."]);
+    }
+
+    #[test]
+    fn reader() {
+        let query = "duct";
+        let contents = io::Cursor::new(
+            "\
+Rust:
+safe, fast, productive.
+Pick three.
+Duct tape.
+Production ready.",
+        );
+
+        assert_eq!(
+            vec!["safe, fast, productive.", "Production ready."],
+            search_reader(query, contents).unwrap()
+        );
+    }
+
+    #[test]
+    fn reader_error() {
+        // 0xFF is never valid in UTF-8
+        let contents = io::Cursor::new(vec![b'o', b'k', b'\n', 0xFF, b'\n']);
+
+        let err = search_reader("ok", contents).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn streaming() {
+        let query = "duct";
+        let contents = io::Cursor::new(
+            "\
+Rust:
+safe, fast, productive.
+Pick three.
+Duct tape.
+Production ready.",
+        );
+        // Vec<u8> implements Write, so it can stand in for stdout
+        let mut out = Vec::new();
+
+        let count = search_streaming(query, contents, &mut out).unwrap();
+
+        assert_eq!(count, 2);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "safe, fast, productive.\nProduction ready.\n"
+        );
+    }
+
+    #[test]
+    fn streaming_no_matches() {
+        let mut out = Vec::new();
+
+        let count = search_streaming(
+            "monomorphization",
+            io::Cursor::new("Rust:\nPick three."),
+            &mut out,
+        )
+        .unwrap();
+
+        assert_eq!(count, 0);
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn parallel_search_reports_every_file() {
+        let config = ConfigBuilder::new()
+            .query("fn")
+            .path("a.rs")
+            .path("b.rs")
+            .path("c.rs")
+            .path("d.rs")
+            .jobs(3)
+            .build()
+            .unwrap();
+        let sources = vec![
+            (
+                String::from("a.rs"),
+                io::Cursor::new("fn main() {}\nlet x = 1;\nfn a() {}"),
+            ),
+            (String::from("b.rs"), io::Cursor::new("struct B;")),
+            (String::from("c.rs"), io::Cursor::new("fn c() {}")),
+            (
+                String::from("d.rs"),
+                io::Cursor::new("fn d1() {}\nfn d2() {}\nfn d3() {}"),
+            ),
+        ];
+        let out = Mutex::new(Vec::new());
+
+        search_parallel(&config, sources, &out).unwrap();
+
+        let output = String::from_utf8(out.into_inner().unwrap()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 6);
+
+        // The files can finish in any order, but the lines from each file stay together and in order
+        for expected in [
+            vec!["a.rs:fn main() {}", "a.rs:fn a() {}"],
+            vec!["c.rs:fn c() {}"],
+            vec!["d.rs:fn d1() {}", "d.rs:fn d2() {}", "d.rs:fn d3() {}"],
+        ] {
+            let start = lines.iter().position(|line| *line == expected[0]).unwrap();
+            assert_eq!(lines[start..start + expected.len()], expected[..]);
+        }
+    }
+
+    #[test]
+    fn parallel_search_reports_read_errors() {
+        let config = ConfigBuilder::new()
+            .query("ok")
+            .path("good.txt")
+            .path("bad.txt")
+            .jobs(2)
+            .build()
+            .unwrap();
+        let sources = vec![
+            (String::from("good.txt"), io::Cursor::new(b"ok\n".to_vec())),
+            (String::from("bad.txt"), io::Cursor::new(vec![0xFF])),
+        ];
+        let out = Mutex::new(Vec::new());
+
+        let err = search_parallel(&config, sources, &out).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn columns() {
+        let query = "duct";
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Duct tape, duct tape.";
+
+        assert_eq!(
+            vec![
+                (2, 16, "safe, fast, productive."),
+                (4, 12, "Duct tape, duct tape.")
+            ],
+            search_with_columns(query, contents, false)
+        );
+        // The first occurrence is at the start of the line when case is ignored
+        assert_eq!(
+            vec![
+                (2, 16, "safe, fast, productive."),
+                (4, 1, "Duct tape, duct tape.")
+            ],
+            search_with_columns(query, contents, true)
+        );
+    }
+
+    #[test]
+    fn columns_count_multi_byte_chars_once() {
+        // 'é' is 2 bytes and '🦀' is 4 bytes, so "rust" starts at byte 11, but at character 8
+        let contents = "café 🦀 rust";
+
+        assert_eq!(
+            vec![(1, 8, "café 🦀 rust")],
+            search_with_columns("rust", contents, false)
+        );
+    }
+
+    #[test]
+    fn columns_count_original_chars_when_ignoring_case() {
+        // 'İ' is 1 character, but its lowercase form "i̇" is 2, so counting in the lowercased line would give column 3
+        assert_eq!(vec![(1, 2, "İx")], search_with_columns("x", "İx", true));
+        assert_eq!(
+            vec![(1, 3, "İİrust")],
+            search_with_columns("RUST", "İİrust", true)
+        );
+    }
+
+    #[test]
+    fn rich_matches() {
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Duct tape, duct tape.";
+
+        assert_eq!(
+            vec![
+                Match {
+                    path: String::from("tape.txt"),
+                    line_number: 2,
+                    column: 16,
+                    text: String::from("safe, fast, productive."),
+                },
+                Match {
+                    path: String::from("tape.txt"),
+                    line_number: 4,
+                    column: 1,
+                    text: String::from("Duct tape, duct tape."),
+                },
+            ],
+            search_rich("duct", "tape.txt", contents, true)
+        );
+    }
+
+    #[test]
+    fn rich_matches_of_nothing() {
+        assert!(search_rich("monomorphization", "poem.txt", "Rust:\nsafe, fast", false).is_empty());
+    }
+
+    #[test]
+    fn column_output() {
+        let config = ConfigBuilder::new()
+            .query("duct")
+            .path("poem.txt")
+            .column(true)
+            .build()
+            .unwrap();
+        let mut out = Vec::new();
+
+        write_results(
+            &config,
+            "poem.txt",
+            "Rust:\nsafe, fast, productive.",
+            &mut out,
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "2:16: safe, fast, productive.\n"
+        );
+    }
+
+    fn write_byte_offsets(contents: &str) -> String {
+        let config = ConfigBuilder::new()
+            .query("duct")
+            .path("input.txt")
+            .byte_offset(true)
+            .build()
+            .unwrap();
+        let mut out = Vec::new();
+
+        write_results(&config, "input.txt", contents, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn byte_offsets_of_first_and_third_lines() {
+        // "duct one\n" is 9 bytes and "nope\n" is 5 bytes, so the third line starts at byte 14
+        assert_eq!(
+            write_byte_offsets("duct one\nnope\nduct three\n"),
+            "0:duct one\n14:duct three\n"
+        );
+    }
+
+    #[test]
+    fn byte_offsets_count_every_newline_byte() {
+        // "\r\n" is 2 bytes, and 'é' is 2 bytes
+        assert_eq!(write_byte_offsets("café\r\n\r\nduct"), "9:duct\n");
+    }
+
+    #[test]
+    fn build_without_file_path() {
+        assert_eq!(
+            Config::build(args(&["minigrep", "to"])).err(),
+            Some("Didn't get a file path")
+        );
+        // With --pattern-file there is no query, so only the file to search is missing
+        assert_eq!(
+            Config::build(args(&["minigrep", "-f", "poem.txt"])).err(),
+            Some("Didn't get a file path")
+        );
+        assert_eq!(
+            Config::build(args(&["minigrep"])).err(),
+            Some("Didn't get a query string")
+        );
+    }
+
+    #[test]
+    fn query_starting_with_dash_after_end_of_flags() {
+        let config = Config::build(args(&["minigrep", "-c", "--", "-> ", "main.rs"])).unwrap();
+        assert_eq!(config.query, "-> ");
+        assert_eq!(config.file_paths, vec!["main.rs"]);
+        assert!(config.count);
+
+        let contents = "fn main() {\nfn parse(s: &str) -> i32 {\nlet x = 5 - 1;";
+        let mut out = Vec::new();
+        write_results(&config, "main.rs", contents, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "1\n");
+
+        // Flags after `--` aren't flags
+        let config = Config::build(args(&["minigrep", "--", "-v", "-q"])).unwrap();
+        assert_eq!(config.query, "-v");
+        assert_eq!(config.file_paths, vec!["-q"]);
+        assert!(!config.invert && !config.quiet);
+
+        // Without `--`, it's still an unknown flag
+        assert_eq!(
+            Config::build(args(&["minigrep", "-1", "poem.txt"])).err(),
+            Some("Unknown flag")
+        );
+    }
+
+    #[test]
+    fn build_parses_byte_offset() {
+        let config = Config::build(args(&["minigrep", "to", "poem.txt", "-b"])).unwrap();
+        assert!(config.byte_offset);
+
+        let result = Config::build(args(&[
+            "minigrep",
+            "to",
+            "poem.txt",
+            "--byte-offset",
+            "--column",
+        ]));
+        assert_eq!(
+            result.err(),
+            Some("--column can't be used with --byte-offset")
+        );
+    }
+
+    #[test]
+    fn null_separated_output() {
+        let config = ConfigBuilder::new()
+            .query("Neil")
+            .path("poem.txt")
+            .null(true)
+            .build()
+            .unwrap();
+        let mut out = Vec::new();
+
+        run_with_writer(config, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Well I heard old Neil put her down\0Well I hope Neil Young will remember\0"
+        );
+    }
+
+    #[test]
+    fn null_separated_count() {
+        let config = Config::build(args(&["minigrep", "-Z", "-c", "Neil", "poem.txt"])).unwrap();
+        let mut out = Vec::new();
+
+        run_with_writer(config, &mut out).unwrap();
+        assert_eq!(out, b"2\0");
+    }
+
+    #[test]
+    fn describe_mentions_each_flag() {
+        let config = Config::build(args(&[
+            "minigrep",
+            "--dry-run",
+            "-v",
+            "-c",
+            "-Z",
+            "-q",
+            "-j",
+            "4",
+            "to",
+            "poem.txt",
+            "notes.txt",
+        ]))
+        .unwrap();
+        let description = config.describe();
+
+        assert!(description.contains("query: \"to\""));
+        assert!(description.contains("files: poem.txt, notes.txt"));
+        for flag in [
+            "--invert",
+            "--count",
+            "--null",
+            "--quiet",
+            "--jobs 4",
+            "--dry-run",
+        ] {
+            assert!(
+                description.contains(flag),
+                "missing {flag} in {description}"
+            );
+        }
+        // Flags that aren't on aren't mentioned
+        assert!(!description.contains("--column"));
+        assert!(!description.contains("--byte-offset"));
+    }
+
+    #[test]
+    fn describe_without_flags() {
+        let config = ConfigBuilder::new()
+            .query("to")
+            .path("poem.txt")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            config.describe(),
+            "query: \"to\"\nfiles: poem.txt\nflags: none"
+        );
+    }
+
+    #[test]
+    fn column_rejects_invert() {
+        let result = ConfigBuilder::new()
+            .query("duct")
+            .column(true)
+            .invert(true)
+            .build();
+
+        assert_eq!(result.err(), Some("--column can't be used with --invert"));
+    }
+
+    #[test]
+    fn run_writes_matches_to_writer() {
+        let config = ConfigBuilder::new()
+            .query("Neil")
+            .path("poem.txt")
+            .build()
+            .unwrap();
+        let mut out = Vec::new();
+
+        run_with_writer(config, &mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Well I heard old Neil put her down\nWell I hope Neil Young will remember\n"
+        );
+    }
+
+    #[test]
+    fn run_with_writer_in_parallel() {
+        // The same file twice, so each one reports the same count
+        let config = ConfigBuilder::new()
+            .query("Neil")
+            .path("poem.txt")
+            .path("poem.txt")
+            .count(true)
+            .jobs(2)
+            .build()
+            .unwrap();
+        let mut out = Vec::new();
+
+        run_with_writer(config, &mut out).unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), "poem.txt:2\npoem.txt:2\n");
+    }
+
+    #[test]
+    fn run_with_writer_reports_missing_file() {
+        let config = ConfigBuilder::new()
+            .query("Neil")
+            .path("does-not-exist.txt")
+            .build()
+            .unwrap();
+        let mut out = Vec::new();
+
+        assert!(run_with_writer(config, &mut out).is_err());
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn build_parses_quiet() {
+        let config = Config::build(args(&["minigrep", "to", "poem.txt", "-q"])).unwrap();
+        assert!(config.quiet);
+
+        let config = Config::build(args(&["minigrep", "to", "poem.txt"])).unwrap();
+        assert!(!config.quiet);
+    }
+
+    fn run_quiet(query: &str, jobs: usize) -> (bool, Vec<u8>) {
+        let config = ConfigBuilder::new()
+            .query(query)
+            .path("poem.txt")
+            .path("poem.txt")
+            .quiet(true)
+            .jobs(jobs)
+            .build()
+            .unwrap();
+        let mut out = Vec::new();
+
+        let matched = run_with_writer(config, &mut out).unwrap();
+        (matched, out)
+    }
+
+    #[test]
+    fn quiet_reports_match_without_output() {
+        for jobs in [1, 2] {
+            let (matched, out) = run_quiet("Neil", jobs);
+            assert!(matched);
+            assert!(out.is_empty());
+        }
+    }
+
+    #[test]
+    fn quiet_reports_no_match() {
+        for jobs in [1, 2] {
+            let (matched, out) = run_quiet("monomorphization", jobs);
+            assert!(!matched);
+            assert!(out.is_empty());
+        }
+    }
+
+    #[test]
+    fn patterns_skip_blank_lines() {
+        let reader = io::Cursor::new("duct\n\n   \nthree\n");
+
+        assert_eq!(read_patterns(reader).unwrap(), vec!["duct", "three"]);
+    }
+
+    #[test]
+    fn line_matches_any_pattern() {
+        let patterns = read_patterns(io::Cursor::new("duct\nthree\n")).unwrap();
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Duct tape.";
+
+        assert_eq!(
+            search_any(&patterns, contents, false),
+            vec!["safe, fast, productive.", "Pick three."]
+        );
+        assert_eq!(
+            search_any(&patterns, contents, true),
+            vec!["safe, fast, productive.", "Pick three.", "Duct tape."]
+        );
+    }
+
+    #[test]
+    fn run_with_patterns() {
+        let config = ConfigBuilder::new()
+            .patterns(vec![String::from("Neil"), String::from("Swampers")])
+            .path("poem.txt")
+            .count(true)
+            .build()
+            .unwrap();
+        let mut out = Vec::new();
+
+        run_with_writer(config, &mut out).unwrap();
+
+        assert_eq!(String::from_utf8(out).unwrap(), "3\n");
+    }
+
+    #[test]
+    fn build_with_pattern_file() {
+        // Every arg that isn't a flag is a file to search
+        let result = Config::build(args(&[
+            "minigrep",
+            "poem.txt",
+            "--pattern-file",
+            "does-not-exist.txt",
+        ]));
+        assert_eq!(result.err(), Some("Couldn't open the pattern file"));
+
+        let result = Config::build(args(&["minigrep", "to", "poem.txt", "-f"]));
+        assert_eq!(result.err(), Some("--pattern-file needs a path"));
+    }
+
+    #[test]
+    fn build_parses_flags_before_query() {
+        let config = Config::build(args(&["minigrep", "-v", "to", "poem.txt"])).unwrap();
+
+        assert_eq!(config.query, "to");
+        assert_eq!(config.file_paths, vec!["poem.txt"]);
+        assert!(config.invert);
+        assert!(config.patterns.is_empty());
+    }
 }
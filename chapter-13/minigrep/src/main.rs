@@ -11,8 +11,25 @@ fn main() {
         process::exit(1);
     });
 
-    if let Err(e) = minigrep::run(config) {
-        eprintln!("Application error: {e}");
-        process::exit(1);
+    // With --dry-run, only show how the arguments were parsed, and don't search anything
+    if config.dry_run {
+        println!("{}", config.describe());
+        return;
+    }
+
+    // run() takes ownership of the config, so check for --quiet first
+    let quiet = config.quiet;
+
+    match minigrep::run(config) {
+        // With --quiet, the exit code is the only output: 0 if anything matched, 1 otherwise (like `grep -q`)
+        Ok(matched) => {
+            if quiet && !matched {
+                process::exit(1);
+            }
+        }
+        Err(e) => {
+            eprintln!("Application error: {e}");
+            process::exit(1);
+        }
     }
 }
@@ -16,6 +16,8 @@ pub use self::kinds::SecondaryColor;
 pub use self::utils::mix;
 
 pub mod kinds {
+    use std::fmt;
+
     pub enum PrimaryColor {
         Red,
         Yellow,
@@ -27,6 +29,75 @@ pub mod kinds {
         Green,
         Purple,
     }
+
+    impl PrimaryColor {
+        /// Returns the red, green and blue components of the color
+        pub fn rgb(&self) -> (u8, u8, u8) {
+            match self {
+                PrimaryColor::Red => (255, 0, 0),
+                PrimaryColor::Yellow => (255, 255, 0),
+                PrimaryColor::Blue => (0, 0, 255),
+            }
+        }
+
+        /// Returns the color as a hex string in the form `"#RRGGBB"`
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// let red = art::PrimaryColor::Red;
+        ///
+        /// assert_eq!("#FF0000", red.hex());
+        /// ```
+        pub fn hex(&self) -> String {
+            to_hex(self.rgb())
+        }
+    }
+
+    impl SecondaryColor {
+        /// Returns the red, green and blue components of the color
+        pub fn rgb(&self) -> (u8, u8, u8) {
+            match self {
+                SecondaryColor::Orange => (255, 165, 0),
+                SecondaryColor::Green => (0, 128, 0),
+                SecondaryColor::Purple => (128, 0, 128),
+            }
+        }
+
+        /// Returns the color as a hex string in the form `"#RRGGBB"`
+        pub fn hex(&self) -> String {
+            to_hex(self.rgb())
+        }
+    }
+
+    // `{:02X}` pads each component to two uppercase hex digits
+    fn to_hex((r, g, b): (u8, u8, u8)) -> String {
+        format!("#{r:02X}{g:02X}{b:02X}")
+    }
+
+    // Implementing Display lets the colors be used directly with `{}` in println! and format!
+    impl fmt::Display for PrimaryColor {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            let name = match self {
+                PrimaryColor::Red => "Red",
+                PrimaryColor::Yellow => "Yellow",
+                PrimaryColor::Blue => "Blue",
+            };
+            // Using pad() instead of write!() means width and alignment options (e.g. `{:>8}`) are respected
+            f.pad(name)
+        }
+    }
+
+    impl fmt::Display for SecondaryColor {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            let name = match self {
+                SecondaryColor::Orange => "Orange",
+                SecondaryColor::Green => "Green",
+                SecondaryColor::Purple => "Purple",
+            };
+            f.pad(name)
+        }
+    }
 }
 
 pub mod utils {
@@ -36,3 +107,42 @@ pub mod utils {
         SecondaryColor::Orange
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn primary_display_names() {
+        assert_eq!(PrimaryColor::Red.to_string(), "Red");
+        assert_eq!(PrimaryColor::Yellow.to_string(), "Yellow");
+        assert_eq!(PrimaryColor::Blue.to_string(), "Blue");
+    }
+
+    #[test]
+    fn secondary_display_names() {
+        assert_eq!(SecondaryColor::Orange.to_string(), "Orange");
+        assert_eq!(SecondaryColor::Green.to_string(), "Green");
+        assert_eq!(SecondaryColor::Purple.to_string(), "Purple");
+    }
+
+    #[test]
+    fn display_respects_width() {
+        assert_eq!(format!("{:>8}", PrimaryColor::Red), "     Red");
+        assert_eq!(format!("{:<8}|", SecondaryColor::Green), "Green   |");
+    }
+
+    #[test]
+    fn primary_hex() {
+        assert_eq!(PrimaryColor::Red.hex(), "#FF0000");
+        assert_eq!(PrimaryColor::Yellow.hex(), "#FFFF00");
+        assert_eq!(PrimaryColor::Blue.hex(), "#0000FF");
+    }
+
+    #[test]
+    fn secondary_hex() {
+        assert_eq!(SecondaryColor::Orange.hex(), "#FFA500");
+        assert_eq!(SecondaryColor::Green.hex(), "#008000");
+        assert_eq!(SecondaryColor::Purple.hex(), "#800080");
+    }
+}
@@ -124,6 +124,104 @@ fn main() {
 // The Box<T> is a smart pointer because it implements the `Deref` trait, which allows Box<T> values to be treated like references
 // When a Box<T> value goes out of scope, the heap data that the box is pointing to is cleaned up due to the `Drop` trait implementation
 
+//
+// Functional Methods on the Cons List
+//
+
+// Note: not in book
+// The List from above (outside of a comment this time), with some of the methods that functional languages have for lists
+// PartialEq and Debug are derived so that lists can be compared with assert_eq!()
+#[derive(Debug, PartialEq)]
+enum List {
+    Cons(i32, Box<List>),
+    Nil,
+}
+
+use crate::List::{Cons, Nil};
+
+impl List {
+    // map() calls `f` on every value and builds a new list out of the results, in the same order
+    // It takes ownership of the list, so each Box can be reused for the new list instead of allocating a new one
+    // `f` is moved into the recursive call, which is fine since each call only needs it once it has called it for its own value
+    fn map(self, f: impl Fn(i32) -> i32) -> List {
+        match self {
+            Cons(value, mut next) => {
+                let value = f(value);
+                *next = next.map(f);
+                Cons(value, next)
+            }
+            Nil => Nil,
+        }
+    }
+
+    // fold() combines every value into a single result, starting from `init` and going from the front of the list to the back
+    // e.g. fold(0, |sum, x| sum + x) works out ((0 + 1) + 2) + 3 for the list (1, (2, (3, Nil)))
+    // The list is only borrowed, so it can still be used afterwards
+    fn fold<T>(&self, init: T, f: impl Fn(T, i32) -> T) -> T {
+        match self {
+            Cons(value, next) => next.fold(f(init, *value), f),
+            Nil => init,
+        }
+    }
+
+    // contains() and position() search the list recursively, the same way print() walks through it
+    // || only calls next.contains() if the current value isn't the target, so the search stops at the first match
+    fn contains(&self, target: i32) -> bool {
+        match self {
+            Cons(value, next) => *value == target || next.contains(target),
+            Nil => false,
+        }
+    }
+
+    // Returns the index of the first value equal to `target`, counting from 0 at the front of the list
+    // The index in the rest of the list is one less than the index in the whole list, so 1 is added on the way back out
+    fn position(&self, target: i32) -> Option<usize> {
+        match self {
+            Cons(value, _) if *value == target => Some(0),
+            Cons(_, next) => next.position(target).map(|index| index + 1),
+            Nil => None,
+        }
+    }
+
+    // append() puts `other` at the end of the list
+    // Each Cons only points forward, so `other` can only be reached by changing the last Cons to point at it instead of Nil
+    // Both lists are consumed, and like map(), each Box of the first list is reused as the list is rebuilt on the way back out
+    fn append(self, other: List) -> List {
+        match self {
+            Cons(value, mut next) => {
+                *next = next.append(other);
+                Cons(value, next)
+            }
+            Nil => other,
+        }
+    }
+
+    // Returns an iterator over the values in the list, which lets us use adaptors like collect() (see chapter 13)
+    fn iter(&self) -> Iter<'_> {
+        Iter { next: self }
+    }
+}
+
+// The iterator only holds a reference to the part of the list that hasn't been visited yet
+struct Iter<'a> {
+    next: &'a List,
+}
+
+impl Iterator for Iter<'_> {
+    type Item = i32;
+
+    fn next(&mut self) -> Option<i32> {
+        match self.next {
+            Cons(value, next) => {
+                // Deref coercion turns the &Box<List> into a &List
+                self.next = next;
+                Some(*value)
+            }
+            Nil => None,
+        }
+    }
+}
+
 // Extra stuff - not in book
 
 fn main() {
@@ -145,4 +243,98 @@ fn main() {
         "Heap address of the value of x through dereferencing z twice: {:p}",
         **z
     );
+
+    // Not in book - see the Functional Methods on the Cons List section
+    let list = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))));
+    let doubled = list.map(|x| x * 2);
+    println!("{:?}", doubled);
+    println!("Sum: {}", doubled.fold(0, |sum, x| sum + x));
+    println!(
+        "Contains 4? {} at {:?}",
+        doubled.contains(4),
+        doubled.position(4)
+    );
+    let combined = doubled.append(Cons(8, Box::new(Nil)));
+    println!("{:?}", combined.iter().collect::<Vec<i32>>());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Builds a cons list from a slice, so the tests don't need to nest Box::new() calls
+    fn list(values: &[i32]) -> List {
+        values
+            .iter()
+            .rev()
+            .fold(Nil, |next, &value| Cons(value, Box::new(next)))
+    }
+
+    #[test]
+    fn map_doubles_every_value() {
+        assert_eq!(list(&[1, 2, 3]).map(|x| x * 2), list(&[2, 4, 6]));
+        assert_eq!(Nil.map(|x| x * 2), Nil);
+    }
+
+    #[test]
+    fn fold_sums_values() {
+        let list = list(&[1, 2, 3, 4]);
+
+        assert_eq!(list.fold(0, |sum, x| sum + x), 10);
+        assert_eq!(Nil.fold(0, |sum, x| sum + x), 0);
+    }
+
+    #[test]
+    fn fold_goes_front_to_back() {
+        let list = list(&[1, 2, 3]);
+
+        // The accumulator doesn't need to be an i32
+        let text = list.fold(String::new(), |text, x| format!("{text}{x}"));
+        assert_eq!(text, "123");
+    }
+
+    #[test]
+    fn finds_present_value() {
+        let list = list(&[5, 7, 9, 7]);
+
+        assert!(list.contains(9));
+        assert_eq!(list.position(5), Some(0));
+        assert_eq!(list.position(9), Some(2));
+        // The first 7 is found, not the last one
+        assert_eq!(list.position(7), Some(1));
+    }
+
+    #[test]
+    fn absent_value() {
+        let list = list(&[5, 7, 9]);
+
+        assert!(!list.contains(8));
+        assert_eq!(list.position(8), None);
+    }
+
+    #[test]
+    fn empty_list_contains_nothing() {
+        assert!(!Nil.contains(0));
+        assert_eq!(Nil.position(0), None);
+    }
+
+    #[test]
+    fn append_keeps_order() {
+        let combined = list(&[1, 2]).append(list(&[3, 4]));
+
+        assert_eq!(combined.iter().collect::<Vec<i32>>(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn append_with_nil() {
+        assert_eq!(
+            Nil.append(list(&[3, 4])).iter().collect::<Vec<i32>>(),
+            vec![3, 4]
+        );
+        assert_eq!(
+            list(&[1, 2]).append(Nil).iter().collect::<Vec<i32>>(),
+            vec![1, 2]
+        );
+        assert_eq!(Nil.append(Nil), Nil);
+    }
 }
@@ -10,6 +10,11 @@
 // Users of the library will be expected to provide the mechanism for sending the messages. (read: a type that implements the Messenger trait)
 // In tests for the limit tracker, we'll provide a mock for the message sending mechanism (read, a mock object that implements the Messenger trait)
 
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
 pub trait Messenger {
     // Note the send method in the Messenger trait,
     // It takes an immutable reference to self and the text of the message
@@ -21,6 +26,8 @@ pub struct LimitTracker<'a, T: Messenger> {
     messenger: &'a T,
     value: usize,
     max: usize,
+    // Note: not in book - the last message sent to the messenger, if any
+    last_message: Option<&'static str>,
 }
 
 impl<'a, T> LimitTracker<'a, T>
@@ -32,30 +39,266 @@ where
             messenger,
             value: 0,
             max,
+            last_message: None,
         }
     }
 
+    // Note: not in book
+    // Callers that don't control the messenger can still see what the tracker last told it
+    // The messages are all string literals, so a &'static str can be stored instead of allocating a String
+    pub fn last_message(&self) -> Option<&str> {
+        self.last_message
+    }
+
+    fn send(&mut self, msg: &'static str) {
+        self.messenger.send(msg);
+        self.last_message = Some(msg);
+    }
+
     // We want to test the behaviour of the set_value() method
     // However, set_value() doesn't return anything for us to make assertions on
     // We want to be able to say that if we create a LimitTracker with something that implements the Messenger trait, and a particular value for max,
     // when we pass different numbers for value, the messneger is told to send the appropiate message
+    // Note: not in book
+    // A tracker with a `max` of 0 has no quota at all, so any value above 0 is over quota, and a value of 0 sends nothing
+    // This is checked up front, since dividing by a `max` of 0 would give NaN (for 0 / 0) or infinity instead of a percentage
     pub fn set_value(&mut self, value: usize) {
         self.value = value;
 
+        if self.max == 0 {
+            if self.value > 0 {
+                self.send("Error: You are over your quota!");
+            }
+            return;
+        }
+
         let percentage_of_max = self.value as f64 / self.max as f64;
 
         if percentage_of_max >= 1.0 {
-            self.messenger.send("Error: You are over your quota!");
+            self.send("Error: You are over your quota!");
         } else if percentage_of_max >= 0.9 {
-            self.messenger
-                .send("Urgent warning: You've used up over 90% of your quota!");
+            self.send("Urgent warning: You've used up over 90% of your quota!");
         } else if percentage_of_max >= 0.75 {
-            self.messenger
-                .send("Warning: You've used up over 75% of your quota!");
+            self.send("Warning: You've used up over 75% of your quota!");
+        }
+    }
+}
+
+// Note: not in book
+// ThresholdMessenger wraps another Messenger, and only passes a message on if it is different from the last message it passed on
+// e.g. calling set_value() with 80 and then 85 would normally send the 75% warning twice, but it only reaches the inner messenger once
+// send() only gets an immutable reference to self, so the last forwarded message is kept in a RefCell<T> to be able to update it
+pub struct ThresholdMessenger<'a, M: Messenger> {
+    inner: &'a M,
+    last_forwarded: RefCell<Option<String>>,
+}
+
+impl<'a, M> ThresholdMessenger<'a, M>
+where
+    M: Messenger,
+{
+    pub fn new(inner: &'a M) -> ThresholdMessenger<'a, M> {
+        ThresholdMessenger {
+            inner,
+            last_forwarded: RefCell::new(None),
+        }
+    }
+}
+
+impl<M> Messenger for ThresholdMessenger<'_, M>
+where
+    M: Messenger,
+{
+    fn send(&self, msg: &str) {
+        let mut last_forwarded = self.last_forwarded.borrow_mut();
+        if last_forwarded.as_deref() != Some(msg) {
+            self.inner.send(msg);
+            *last_forwarded = Some(String::from(msg));
+        }
+    }
+}
+
+// Note: not in book
+// A Subject sends each message to every subscribed Messenger (the observer pattern), instead of the single messenger a LimitTracker has
+// The subscribers can be different types, so they are stored as trait objects
+pub struct Subject {
+    observers: Vec<Box<dyn Messenger>>,
+}
+
+impl Subject {
+    pub fn new() -> Subject {
+        Subject {
+            observers: Vec::new(),
+        }
+    }
+
+    pub fn subscribe(&mut self, observer: Box<dyn Messenger>) {
+        self.observers.push(observer);
+    }
+
+    pub fn notify_all(&self, msg: &str) {
+        for observer in self.observers.iter() {
+            observer.send(msg);
+        }
+    }
+}
+
+impl Default for Subject {
+    fn default() -> Self {
+        Subject::new()
+    }
+}
+
+// A Subject owns its observers, so a caller that wants to keep using an observer after subscribing it can share it with an Rc<T>
+// e.g. `subject.subscribe(Box::new(Rc::clone(&observer)))`
+impl<M> Messenger for Rc<M>
+where
+    M: Messenger + ?Sized,
+{
+    fn send(&self, msg: &str) {
+        (**self).send(msg);
+    }
+}
+
+// Note: not in book
+// FilteredMessenger only passes on the messages that `predicate` returns true for, so a subscriber can pick which messages it gets
+// Unlike ThresholdMessenger, it owns the messenger it wraps, so it can be subscribed to a Subject (which needs an owned Box<dyn Messenger>)
+pub struct FilteredMessenger<M, F>
+where
+    M: Messenger,
+    F: Fn(&str) -> bool,
+{
+    inner: M,
+    predicate: F,
+}
+
+impl<M, F> FilteredMessenger<M, F>
+where
+    M: Messenger,
+    F: Fn(&str) -> bool,
+{
+    pub fn new(inner: M, predicate: F) -> FilteredMessenger<M, F> {
+        FilteredMessenger { inner, predicate }
+    }
+}
+
+impl<M, F> Messenger for FilteredMessenger<M, F>
+where
+    M: Messenger,
+    F: Fn(&str) -> bool,
+{
+    fn send(&self, msg: &str) {
+        if (self.predicate)(msg) {
+            self.inner.send(msg);
+        }
+    }
+}
+
+// Note: not in book
+// ThrottleMessenger drops any message that arrives less than `interval` after the last message it passed on, to rate-limit alerts
+// The time the last message was passed on is kept in a RefCell<T>, since send() only gets an immutable reference to self
+// `clock` returns the current time, new() uses Instant::now(), but with_clock() lets tests control the time instead of sleeping
+pub struct ThrottleMessenger<M, C>
+where
+    M: Messenger,
+    C: Fn() -> Instant,
+{
+    inner: M,
+    interval: Duration,
+    clock: C,
+    last_forwarded: RefCell<Option<Instant>>,
+}
+
+impl<M> ThrottleMessenger<M, fn() -> Instant>
+where
+    M: Messenger,
+{
+    pub fn new(inner: M, interval: Duration) -> ThrottleMessenger<M, fn() -> Instant> {
+        ThrottleMessenger::with_clock(inner, interval, Instant::now)
+    }
+}
+
+impl<M, C> ThrottleMessenger<M, C>
+where
+    M: Messenger,
+    C: Fn() -> Instant,
+{
+    pub fn with_clock(inner: M, interval: Duration, clock: C) -> ThrottleMessenger<M, C> {
+        ThrottleMessenger {
+            inner,
+            interval,
+            clock,
+            last_forwarded: RefCell::new(None),
+        }
+    }
+}
+
+impl<M, C> Messenger for ThrottleMessenger<M, C>
+where
+    M: Messenger,
+    C: Fn() -> Instant,
+{
+    fn send(&self, msg: &str) {
+        let now = (self.clock)();
+        let mut last_forwarded = self.last_forwarded.borrow_mut();
+
+        // Dropped messages don't reset the timer, only forwarded ones do
+        if let Some(last) = *last_forwarded {
+            if now.duration_since(last) < self.interval {
+                return;
+            }
+        }
+        self.inner.send(msg);
+        *last_forwarded = Some(now);
+    }
+}
+
+// Note: not in book
+// EventBus is a Subject that can be shared between threads, so messages can be published from more than one thread at once
+// Subject uses Rc<T> and RefCell<T>, which aren't thread-safe, so EventBus uses their thread-safe versions (see chapter 16):
+// - Arc<T> instead of Rc<T>, so each thread can own a handle to the same list of subscribers (see clone())
+// - Mutex<T> instead of RefCell<T>, so only one thread can use the list at a time
+// The subscribers are `Send` so they can be used from whichever thread publishes a message
+// They don't need to be `Sync`, since the Mutex makes sure only one thread calls send() at a time (so a subscriber can still use a RefCell<T> inside)
+pub struct EventBus {
+    subscribers: Arc<Mutex<Vec<Box<dyn Messenger + Send>>>>,
+}
+
+impl EventBus {
+    pub fn new() -> EventBus {
+        EventBus {
+            subscribers: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    pub fn subscribe(&self, subscriber: Box<dyn Messenger + Send>) {
+        self.subscribers.lock().unwrap().push(subscriber);
+    }
+
+    // The lock is held while every subscriber is sent the message, so messages published from different threads are never interleaved
+    // A slow subscriber holds up every other thread that is publishing
+    pub fn publish(&self, msg: &str) {
+        for subscriber in self.subscribers.lock().unwrap().iter() {
+            subscriber.send(msg);
+        }
+    }
+}
+
+// Cloning an EventBus only clones the Arc<T>, so both handles publish to the same subscribers
+impl Clone for EventBus {
+    fn clone(&self) -> EventBus {
+        EventBus {
+            subscribers: Arc::clone(&self.subscribers),
         }
     }
 }
 
+impl Default for EventBus {
+    fn default() -> Self {
+        EventBus::new()
+    }
+}
+
 // We need a mock object that will only keep track of the messages it is told to send.
 // We can create a new instance of the mock object,
 // create a LimitTracker that uses the mock object,
@@ -103,7 +346,6 @@ mod tests {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::cell::RefCell;
 
     struct MockMessenger {
         // Wrap the vec in sent_messages in a RefCell<T>
@@ -137,6 +379,200 @@ mod tests {
         // Call borrow() on the RefCell<T> to get an immutable reference
         assert_eq!(mock_messenger.sent_messages.borrow().len(), 1);
     }
+
+    #[test]
+    fn last_message_follows_set_value() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+        assert_eq!(limit_tracker.last_message(), None);
+
+        // Nothing is sent under 75%
+        limit_tracker.set_value(10);
+        assert_eq!(limit_tracker.last_message(), None);
+
+        limit_tracker.set_value(80);
+        assert_eq!(
+            limit_tracker.last_message(),
+            Some("Warning: You've used up over 75% of your quota!")
+        );
+
+        limit_tracker.set_value(95);
+        assert_eq!(
+            limit_tracker.last_message(),
+            Some("Urgent warning: You've used up over 90% of your quota!")
+        );
+
+        limit_tracker.set_value(100);
+        assert_eq!(
+            limit_tracker.last_message(),
+            Some("Error: You are over your quota!")
+        );
+
+        // Going back under 75% doesn't send anything, so the last message stays the same
+        limit_tracker.set_value(0);
+        assert_eq!(
+            limit_tracker.last_message(),
+            Some("Error: You are over your quota!")
+        );
+        assert_eq!(mock_messenger.sent_messages.borrow().len(), 3);
+    }
+
+    #[test]
+    fn threshold_messenger_suppresses_duplicates() {
+        let mock_messenger = MockMessenger::new();
+        let threshold_messenger = ThresholdMessenger::new(&mock_messenger);
+        let mut limit_tracker = LimitTracker::new(&threshold_messenger, 100);
+
+        limit_tracker.set_value(80);
+        limit_tracker.set_value(85);
+        limit_tracker.set_value(92);
+        limit_tracker.set_value(95);
+        // A message that changes back is forwarded again
+        limit_tracker.set_value(80);
+
+        assert_eq!(
+            *mock_messenger.sent_messages.borrow(),
+            vec![
+                "Warning: You've used up over 75% of your quota!",
+                "Urgent warning: You've used up over 90% of your quota!",
+                "Warning: You've used up over 75% of your quota!",
+            ]
+        );
+    }
+
+    #[test]
+    fn subject_notifies_every_observer() {
+        let first = Rc::new(MockMessenger::new());
+        let second = Rc::new(MockMessenger::new());
+
+        let mut subject = Subject::new();
+        subject.subscribe(Box::new(Rc::clone(&first)));
+        subject.subscribe(Box::new(Rc::clone(&second)));
+
+        subject.notify_all("Server restarting");
+        subject.notify_all("Server back up");
+
+        let expected = vec!["Server restarting", "Server back up"];
+        assert_eq!(*first.sent_messages.borrow(), expected);
+        assert_eq!(*second.sent_messages.borrow(), expected);
+    }
+
+    #[test]
+    fn filtered_observer_misses_other_messages() {
+        let everything = Rc::new(MockMessenger::new());
+        let errors_only = Rc::new(MockMessenger::new());
+
+        let mut subject = Subject::new();
+        subject.subscribe(Box::new(Rc::clone(&everything)));
+        subject.subscribe(Box::new(FilteredMessenger::new(
+            Rc::clone(&errors_only),
+            |msg: &str| msg.contains("Error"),
+        )));
+
+        subject.notify_all("Warning: disk 80% full");
+        subject.notify_all("Error: disk full");
+        subject.notify_all("Disk cleaned up");
+
+        assert_eq!(everything.sent_messages.borrow().len(), 3);
+        assert_eq!(
+            *errors_only.sent_messages.borrow(),
+            vec!["Error: disk full"]
+        );
+    }
+
+    #[test]
+    fn throttle_drops_messages_within_interval() {
+        use std::cell::Cell;
+
+        // A fake clock that only moves when the test moves it
+        let start = Instant::now();
+        let now = Rc::new(Cell::new(start));
+        let clock = {
+            let now = Rc::clone(&now);
+            move || now.get()
+        };
+
+        let mock_messenger = Rc::new(MockMessenger::new());
+        let throttle = ThrottleMessenger::with_clock(
+            Rc::clone(&mock_messenger),
+            Duration::from_secs(10),
+            clock,
+        );
+
+        throttle.send("first");
+        now.set(start + Duration::from_secs(5));
+        throttle.send("too soon");
+        now.set(start + Duration::from_secs(10));
+        throttle.send("second");
+        now.set(start + Duration::from_secs(19));
+        throttle.send("still too soon");
+
+        assert_eq!(
+            *mock_messenger.sent_messages.borrow(),
+            vec!["first", "second"]
+        );
+    }
+
+    // A subscriber for EventBus, which counts the messages it gets in a count that the test can also see
+    struct CountingMessenger {
+        received: Arc<Mutex<usize>>,
+    }
+
+    impl Messenger for CountingMessenger {
+        fn send(&self, _msg: &str) {
+            *self.received.lock().unwrap() += 1;
+        }
+    }
+
+    #[test]
+    fn event_bus_receives_from_every_thread() {
+        use std::thread;
+
+        let received = Arc::new(Mutex::new(0));
+        let bus = EventBus::new();
+        bus.subscribe(Box::new(CountingMessenger {
+            received: Arc::clone(&received),
+        }));
+
+        let handles: Vec<_> = (0..2)
+            .map(|id| {
+                let bus = bus.clone();
+                thread::spawn(move || {
+                    for n in 0..100 {
+                        bus.publish(&format!("thread {id}: message {n}"));
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(*received.lock().unwrap(), 200);
+    }
+
+    #[test]
+    fn zero_max_with_zero_value_sends_nothing() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 0);
+
+        limit_tracker.set_value(0);
+
+        assert!(mock_messenger.sent_messages.borrow().is_empty());
+    }
+
+    #[test]
+    fn zero_max_with_any_value_is_over_quota() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 0);
+
+        limit_tracker.set_value(1);
+
+        assert_eq!(
+            *mock_messenger.sent_messages.borrow(),
+            vec!["Error: You are over your quota!"]
+        );
+    }
 }
 
 //
@@ -195,4 +631,3 @@ mod tests {
 
 // Choosing to catch borrowing errors at runtime rather than compile time might mean mistakes will pop up later down the line (such as in prod)
 // The code will also incur a small runtime penalty
-
@@ -63,6 +63,80 @@ fn main() {
 }
 */
 
+// Note: not in book
+// The List from above, outside of a comment, renamed to MutList to show that it can be changed after it's built
+// The tail is behind a RefCell, so it can be swapped for another list through a shared reference (interior mutability)
+// Swapping in a list that doesn't lead back to this one is fine, the cycle above only happens because `a` was pointed at `b`, which already pointed at `a`
+#[derive(Debug)]
+enum MutList {
+    Cons(i32, RefCell<Rc<MutList>>),
+    Nil,
+}
+
+impl MutList {
+    fn tail(&self) -> Option<&RefCell<Rc<MutList>>> {
+        match self {
+            MutList::Cons(_, item) => Some(item),
+            MutList::Nil => None,
+        }
+    }
+
+    // set_tail() only needs `&self`, since borrow_mut() on the RefCell gives us a mutable reference to the tail
+    // The old tail is dropped when it's replaced, which drops the list it points to if nothing else owns it
+    // Nil doesn't have a tail, so there's nothing to change
+    fn set_tail(&self, new_tail: Rc<MutList>) {
+        if let Some(link) = self.tail() {
+            *link.borrow_mut() = new_tail;
+        }
+    }
+
+    // Collects the values by following the tails, so we can see what the list looks like after it has been changed
+    // This would loop forever on a list with a cycle
+    fn values(&self) -> Vec<i32> {
+        let MutList::Cons(value, tail) = self else {
+            return Vec::new();
+        };
+        let mut values = vec![*value];
+        let mut current = Rc::clone(&tail.borrow());
+        // Each tail is cloned out of its RefCell, so the borrow() ends before we move on to the next list
+        while let MutList::Cons(value, tail) = &*current {
+            values.push(*value);
+            let next = Rc::clone(&tail.borrow());
+            current = next;
+        }
+        values
+    }
+}
+
+// Note: not in book
+// Rust can't catch cycles at compile time, but we can check a MutList for one at runtime
+// Following the tails until Nil would never finish on a list with a cycle, and keeping track of every list we've visited would need more memory as the list gets longer
+// Floyd's cycle detection ("tortoise and hare") uses two pointers instead: `slow` moves one step at a time and `fast` moves two steps
+// If `fast` reaches Nil, there is no cycle. If there is a cycle, `fast` goes around it and eventually lands on the same list as `slow`
+// This is a loop rather than recursion, so a long list can't overflow the stack
+fn has_cycle(start: &Rc<MutList>) -> bool {
+    // Returns the list that `list` points to, or None for Nil
+    // The tail is cloned out of the RefCell, so the borrow() doesn't outlive this function
+    fn step(list: &Rc<MutList>) -> Option<Rc<MutList>> {
+        list.tail().map(|tail| Rc::clone(&tail.borrow()))
+    }
+
+    let mut slow = Rc::clone(start);
+    let mut fast = Rc::clone(start);
+    loop {
+        fast = match step(&fast).and_then(|next| step(&next)) {
+            Some(list) => list,
+            None => return false,
+        };
+        // `fast` has already been through here, so `slow` can't reach Nil first
+        slow = step(&slow).unwrap();
+        // Rc::ptr_eq() checks whether both point to the same list, not whether the lists have the same values
+        if Rc::ptr_eq(&slow, &fast) {
+            return true;
+        }
+    }
+}
+
 // If a more complex program allocated lots of memory in a cycle, and held it for a long itme, the program would use more memory than usual, and might crash the system
 // Creating reference cycle is not easily done, but not impossible
 // Be careful with nested Rc<T> in RefCell<T> instances, or similar nested combinations of types with interior mutability and reference counting.
@@ -175,6 +249,105 @@ fn main() {
 }
 */
 
+// Note: not in book
+// fold_tree() visits every node in depth-first order (a node, then each of its children's subtrees), combining the values with `f`
+// Only the strong `children` links are followed, so each node is visited once even though children also point back to their parents
+// borrow() on the RefCell only lasts while we iterate over the children, and doesn't conflict since nothing is borrowed mutably
+fn fold_tree<T>(root: &Rc<Node>, init: T, f: &impl Fn(T, i32) -> T) -> T {
+    let acc = f(init, root.value);
+    root.children
+        .borrow()
+        .iter()
+        .fold(acc, |acc, child| fold_tree(child, acc, f))
+}
+
+// Note: not in book
+// A node doesn't know about its siblings directly, but it can reach them through its parent
+// upgrade() returns None if the node has no parent (or the parent has been dropped), and `?` returns None from next_sibling() in that case
+// Rc::ptr_eq() compares the pointers rather than the values, so two different nodes with the same value aren't mixed up
+fn next_sibling(node: &Rc<Node>) -> Option<Rc<Node>> {
+    let parent = node.parent.borrow().upgrade()?;
+    let siblings = parent.children.borrow();
+    let index = siblings.iter().position(|child| Rc::ptr_eq(child, node))?;
+    siblings.get(index + 1).map(Rc::clone)
+}
+
+// Note: not in book
+// detach() removes `node` from its parent's children, which drops the parent's strong reference to it
+// The node's `parent` is then replaced with an empty Weak<Node>, so the detached subtree becomes a tree of its own
+// The parent's children need to be borrowed mutably, which is fine as long as nothing else is borrowing them at the same time
+fn detach(node: &Rc<Node>) {
+    if let Some(parent) = node.parent.borrow().upgrade() {
+        parent
+            .children
+            .borrow_mut()
+            .retain(|child| !Rc::ptr_eq(child, node));
+    }
+    *node.parent.borrow_mut() = Weak::new();
+}
+
+//
+// Observers That Can Be Dropped
+//
+
+// Note: not in book
+// In the observer pattern, a subject keeps a list of observers and notifies each of them when something happens
+// If the subject held each observer in an Rc<T>, an observer couldn't be dropped until the subject was, even after everything else was done with it
+// (and an observer that also held the subject in an Rc<T> would make a cycle)
+// Like a node's `parent`, the subject holds Weak<T> references instead, so it doesn't own its observers
+// The event type `E` is generic, so the same Subject works for any kind of event
+trait Observer<E> {
+    fn notify(&self, event: &E);
+}
+
+struct Subject<E> {
+    // A Vec can only hold one type, so each observer is a trait object
+    observers: Vec<Weak<dyn Observer<E>>>,
+}
+
+impl<E> Subject<E> {
+    fn new() -> Subject<E> {
+        Subject {
+            observers: Vec::new(),
+        }
+    }
+
+    // Rc::downgrade() gives us a Weak<O>, which is turned into a Weak<dyn Observer<E>> when it's pushed onto the Vec
+    // The trait object can't hold any borrowed references, which is what the `'static` bound asks for
+    fn subscribe<O: Observer<E> + 'static>(&mut self, observer: &Rc<O>) {
+        let observer: Weak<O> = Rc::downgrade(observer);
+        self.observers.push(observer);
+    }
+
+    // Notifies every observer that is still alive
+    // upgrade() returns None for an observer that has been dropped, and retain() removes it, so it isn't checked again next time
+    fn notify(&mut self, event: &E) {
+        self.observers.retain(|observer| match observer.upgrade() {
+            Some(observer) => {
+                observer.notify(event);
+                true
+            }
+            None => false,
+        });
+    }
+
+    // The number of observers the subject is holding, which can include dropped observers until the next notify()
+    fn observer_count(&self) -> usize {
+        self.observers.len()
+    }
+}
+
+// An observer that prints every event it's notified about
+struct Logger {
+    name: String,
+}
+
+impl Observer<String> for Logger {
+    fn notify(&self, event: &String) {
+        println!("{} got '{event}'", self.name);
+    }
+}
+
 //
 // Visualizing Changes to `strong_count` and `weak_count`
 //
@@ -215,6 +388,16 @@ fn main() {
             Rc::strong_count(&leaf),
             Rc::weak_count(&leaf),
         );
+
+        // Note: not in book
+        // leaf next sibling = None, since leaf is the only child of branch
+        println!("leaf next sibling = {:?}", next_sibling(&leaf));
+
+        // sum of values in branch = 8
+        println!(
+            "sum of values in branch = {}",
+            fold_tree(&branch, 0, &|acc, value| acc + value)
+        );
     }
 
     // leaf parent = None
@@ -225,4 +408,315 @@ fn main() {
         Rc::strong_count(&leaf),
         Rc::weak_count(&leaf),
     );
+
+    // Note: not in book
+    // detach() removes a child from its parent while both are still alive
+    let branch = Rc::new(Node {
+        value: 5,
+        parent: RefCell::new(Weak::new()),
+        children: RefCell::new(vec![Rc::clone(&leaf)]),
+    });
+    *leaf.parent.borrow_mut() = Rc::downgrade(&branch);
+
+    detach(&leaf);
+
+    // leaf parent = None, branch children = 0
+    println!(
+        "leaf parent = {:?}, branch children = {}",
+        leaf.parent.borrow().upgrade(),
+        branch.children.borrow().len(),
+    );
+
+    // Note: not in book
+    // Changing the tail of a MutList without making a cycle
+    let a = Rc::new(MutList::Cons(5, RefCell::new(Rc::new(MutList::Nil))));
+    let b = Rc::new(MutList::Cons(10, RefCell::new(Rc::new(MutList::Nil))));
+    a.set_tail(Rc::clone(&b));
+
+    // a = [5, 10]
+    println!("a = {:?}", a.values());
+
+    // Note: not in book
+    // a has a cycle = false, then true after pointing b back at a
+    println!("a has a cycle = {}", has_cycle(&a));
+    b.set_tail(Rc::clone(&a));
+    println!("a has a cycle = {}", has_cycle(&a));
+    // Break the cycle again, so the lists can be dropped at the end of main()
+    b.set_tail(Rc::new(MutList::Nil));
+
+    // Note: not in book
+    let mut subject = Subject::new();
+    let first = Rc::new(Logger {
+        name: String::from("first"),
+    });
+    {
+        let second = Rc::new(Logger {
+            name: String::from("second"),
+        });
+        subject.subscribe(&first);
+        subject.subscribe(&second);
+        // first got 'hello', second got 'hello'
+        subject.notify(&String::from("hello"));
+    }
+    // first got 'goodbye', since `second` was dropped at the end of the scope above
+    subject.notify(&String::from("goodbye"));
+    // observers = 1
+    println!("observers = {}", subject.observer_count());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Creates a node that owns `children`, and points each child's `parent` back to the new node
+    fn node(value: i32, children: Vec<Rc<Node>>) -> Rc<Node> {
+        let parent = Rc::new(Node {
+            value,
+            parent: RefCell::new(Weak::new()),
+            children: RefCell::new(children),
+        });
+        for child in parent.children.borrow().iter() {
+            *child.parent.borrow_mut() = Rc::downgrade(&parent);
+        }
+        parent
+    }
+
+    //      1
+    //    / | \
+    //   2  3  4
+    //  / \
+    // 5   6
+    fn sample_tree() -> Rc<Node> {
+        node(
+            1,
+            vec![
+                node(2, vec![node(5, vec![]), node(6, vec![])]),
+                node(3, vec![]),
+                node(4, vec![]),
+            ],
+        )
+    }
+
+    fn cons(value: i32, tail: Rc<MutList>) -> Rc<MutList> {
+        Rc::new(MutList::Cons(value, RefCell::new(tail)))
+    }
+
+    #[test]
+    fn set_tail_changes_the_list() {
+        let shared = cons(3, Rc::new(MutList::Nil));
+        let a = cons(1, cons(2, Rc::clone(&shared)));
+        assert_eq!(a.values(), vec![1, 2, 3]);
+
+        // Skip over 2, straight to the list that starts with 3
+        a.set_tail(Rc::clone(&shared));
+        assert_eq!(a.values(), vec![1, 3]);
+
+        // The tail of 3 can be changed through `shared`, and `a` sees the change
+        shared.set_tail(cons(4, Rc::new(MutList::Nil)));
+        assert_eq!(a.values(), vec![1, 3, 4]);
+
+        // Owned by `shared` and the tail of `a`
+        assert_eq!(Rc::strong_count(&shared), 2);
+    }
+
+    #[test]
+    fn set_tail_on_nil_does_nothing() {
+        let nil = MutList::Nil;
+
+        nil.set_tail(cons(1, Rc::new(MutList::Nil)));
+
+        assert!(nil.tail().is_none());
+        assert_eq!(nil.values(), vec![]);
+    }
+
+    #[test]
+    fn detects_cycle() {
+        // 1 -> 2 -> 3 -> 4 -> back to 2
+        let last = cons(4, Rc::new(MutList::Nil));
+        let second = cons(2, cons(3, Rc::clone(&last)));
+        let first = cons(1, Rc::clone(&second));
+        last.set_tail(Rc::clone(&second));
+
+        assert!(has_cycle(&first));
+        assert!(has_cycle(&second));
+
+        // Break the cycle, otherwise the lists would never be dropped
+        last.set_tail(Rc::new(MutList::Nil));
+        assert!(!has_cycle(&first));
+    }
+
+    #[test]
+    fn detects_list_pointing_at_itself() {
+        let list = cons(1, Rc::new(MutList::Nil));
+        list.set_tail(Rc::clone(&list));
+
+        assert!(has_cycle(&list));
+
+        list.set_tail(Rc::new(MutList::Nil));
+    }
+
+    #[test]
+    fn acyclic_list_has_no_cycle() {
+        // Two lists sharing a tail isn't a cycle
+        let shared = cons(3, cons(4, Rc::new(MutList::Nil)));
+        let a = cons(1, Rc::clone(&shared));
+        let b = cons(2, Rc::clone(&shared));
+
+        assert!(!has_cycle(&a));
+        assert!(!has_cycle(&b));
+        assert!(!has_cycle(&Rc::new(MutList::Nil)));
+    }
+
+    // Keeps every event it's notified about, so the tests can check who was notified
+    struct Recorder {
+        events: RefCell<Vec<i32>>,
+    }
+
+    impl Observer<i32> for Recorder {
+        fn notify(&self, event: &i32) {
+            self.events.borrow_mut().push(*event);
+        }
+    }
+
+    fn recorder() -> Rc<Recorder> {
+        Rc::new(Recorder {
+            events: RefCell::new(vec![]),
+        })
+    }
+
+    #[test]
+    fn dropped_observer_is_pruned() {
+        let mut subject = Subject::new();
+        let kept = recorder();
+        let dropped = recorder();
+        subject.subscribe(&kept);
+        subject.subscribe(&dropped);
+
+        subject.notify(&1);
+        assert_eq!(*dropped.events.borrow(), vec![1]);
+
+        // The subject only has a weak reference, so `dropped` was the last strong reference
+        let weak = Rc::downgrade(&dropped);
+        drop(dropped);
+        assert!(weak.upgrade().is_none());
+        // The dead observer isn't removed until the next notify()
+        assert_eq!(subject.observer_count(), 2);
+
+        subject.notify(&2);
+
+        assert_eq!(subject.observer_count(), 1);
+        assert_eq!(*kept.events.borrow(), vec![1, 2]);
+    }
+
+    #[test]
+    fn subject_does_not_keep_observers_alive() {
+        let mut subject = Subject::new();
+        let observer = recorder();
+        subject.subscribe(&observer);
+
+        assert_eq!(Rc::strong_count(&observer), 1);
+        assert_eq!(Rc::weak_count(&observer), 1);
+    }
+
+    #[test]
+    fn fold_tree_sums_values() {
+        let root = sample_tree();
+        assert_eq!(fold_tree(&root, 0, &|acc, value| acc + value), 21);
+    }
+
+    #[test]
+    fn fold_tree_counts_nodes() {
+        let root = sample_tree();
+        assert_eq!(fold_tree(&root, 0, &|count, _| count + 1), 6);
+    }
+
+    #[test]
+    fn fold_tree_visits_depth_first() {
+        let root = sample_tree();
+        let order = fold_tree(&root, vec![], &|mut order, value| {
+            order.push(value);
+            order
+        });
+        assert_eq!(order, vec![1, 2, 5, 6, 3, 4]);
+    }
+
+    #[test]
+    fn next_sibling_of_middle_child() {
+        let root = sample_tree();
+        let middle = Rc::clone(&root.children.borrow()[1]);
+
+        let sibling = next_sibling(&middle).unwrap();
+        assert_eq!(sibling.value, 4);
+        assert!(Rc::ptr_eq(&sibling, &root.children.borrow()[2]));
+    }
+
+    #[test]
+    fn next_sibling_of_last_child() {
+        let root = sample_tree();
+        let last = Rc::clone(&root.children.borrow()[2]);
+
+        assert!(next_sibling(&last).is_none());
+    }
+
+    #[test]
+    fn next_sibling_of_root() {
+        let root = sample_tree();
+
+        assert!(next_sibling(&root).is_none());
+    }
+
+    #[test]
+    fn next_sibling_with_equal_values() {
+        // Siblings are found by pointer, not by value
+        let root = node(0, vec![node(7, vec![]), node(7, vec![])]);
+        let first = Rc::clone(&root.children.borrow()[0]);
+        let second = Rc::clone(&root.children.borrow()[1]);
+
+        assert!(Rc::ptr_eq(&next_sibling(&first).unwrap(), &second));
+        assert!(next_sibling(&second).is_none());
+    }
+
+    #[test]
+    fn detach_removes_child_from_parent() {
+        let root = sample_tree();
+        let child = Rc::clone(&root.children.borrow()[0]);
+        // Owned by `root.children` and `child`
+        assert_eq!(Rc::strong_count(&child), 2);
+
+        detach(&child);
+
+        assert!(child.parent.borrow().upgrade().is_none());
+        assert!(!root.children.borrow().iter().any(|c| Rc::ptr_eq(c, &child)));
+        assert_eq!(root.children.borrow().len(), 2);
+        // Only `child` owns the node now
+        assert_eq!(Rc::strong_count(&child), 1);
+    }
+
+    #[test]
+    fn detach_keeps_subtree_intact() {
+        let root = sample_tree();
+        let child = Rc::clone(&root.children.borrow()[0]);
+
+        detach(&child);
+
+        // The detached node still owns its children, and they still point back to it
+        assert_eq!(fold_tree(&child, 0, &|acc, value| acc + value), 13);
+        let grandchild = Rc::clone(&child.children.borrow()[0]);
+        assert!(Rc::ptr_eq(
+            &grandchild.parent.borrow().upgrade().unwrap(),
+            &child
+        ));
+        // The rest of the tree no longer includes the subtree
+        assert_eq!(fold_tree(&root, 0, &|acc, value| acc + value), 8);
+    }
+
+    #[test]
+    fn detach_root_does_nothing() {
+        let root = sample_tree();
+
+        detach(&root);
+
+        assert_eq!(root.children.borrow().len(), 3);
+        assert_eq!(Rc::strong_count(&root), 1);
+    }
 }
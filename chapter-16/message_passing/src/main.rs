@@ -145,4 +145,183 @@ fn main() {
         // Will print out messages from both threads interleaved
         println!("Got: {}", received);
     }
+
+    // Note: not in book - see the section after main()
+    let mut news = Broadcast::new();
+    let first = news.subscribe();
+    let second = news.subscribe();
+    news.send(String::from("extra extra"));
+    println!("First got: {}", first.recv().unwrap());
+    println!("Second got: {}", second.recv().unwrap());
+
+    let (tx_a, rx_a) = mpsc::channel();
+    let (tx_b, rx_b) = mpsc::channel();
+    tx_a.send((1, "a1")).unwrap();
+    tx_a.send((4, "a4")).unwrap();
+    tx_b.send((2, "b2")).unwrap();
+    tx_b.send((3, "b3")).unwrap();
+    drop(tx_a);
+    drop(tx_b);
+    for received in merge_ordered(rx_a, rx_b) {
+        println!("Merged: {}", received);
+    }
+}
+
+//
+// Sending Every Message to Multiple Consumers
+//
+
+// Note: not in book
+// A channel only has one receiver, so each message is only received once
+// Broadcast keeps a transmitter for every subscriber, and sends each subscriber its own clone of every message (which is why T needs to be Clone)
+pub struct Broadcast<T: Clone> {
+    senders: Vec<mpsc::Sender<T>>,
+}
+
+impl<T: Clone> Broadcast<T> {
+    pub fn new() -> Broadcast<T> {
+        Broadcast {
+            senders: Vec::new(),
+        }
+    }
+
+    // Each subscriber gets its own channel, and only receives the messages sent after it subscribed
+    pub fn subscribe(&mut self) -> mpsc::Receiver<T> {
+        let (tx, rx) = mpsc::channel();
+        self.senders.push(tx);
+        rx
+    }
+
+    // send() returns an Err once the receiver has been dropped, so those subscribers are removed instead of sending to them forever
+    pub fn send(&mut self, msg: T) {
+        self.senders.retain(|tx| tx.send(msg.clone()).is_ok());
+    }
+}
+
+impl<T: Clone> Default for Broadcast<T> {
+    fn default() -> Self {
+        Broadcast::new()
+    }
+}
+
+//
+// Merging Channels in Order
+//
+
+// Note: not in book
+// merge_ordered() combines two channels of (timestamp, message) pairs into one channel, with the messages in timestamp order
+// Each input channel must already be in timestamp order (e.g. each one is fed by a single producer that timestamps its own events)
+// A spawned thread holds the next message from each channel, and always sends on whichever of the two has the earlier timestamp
+// It has to wait for both channels to have a message (or to close) before sending, since the other channel could still have an earlier message coming
+fn merge_ordered<T: Send + 'static>(
+    a: mpsc::Receiver<(u64, T)>,
+    b: mpsc::Receiver<(u64, T)>,
+) -> mpsc::Receiver<T> {
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        // recv() returns an Err once a channel is closed, which ok() turns into None
+        let mut next_a = a.recv().ok();
+        let mut next_b = b.recv().ok();
+
+        loop {
+            // Take the earlier message out of the buffer, and replace it with the next message from the same channel
+            // On a tie, the message from `a` goes first
+            let msg = match (&next_a, &next_b) {
+                (Some((time_a, _)), Some((time_b, _))) if time_a <= time_b => {
+                    std::mem::replace(&mut next_a, a.recv().ok())
+                }
+                (_, Some(_)) => std::mem::replace(&mut next_b, b.recv().ok()),
+                (Some(_), None) => std::mem::replace(&mut next_a, a.recv().ok()),
+                // Both channels are closed and empty
+                (None, None) => break,
+            };
+
+            // The buffer we took from was Some, so unwrap() can't panic
+            let (_, value) = msg.unwrap();
+            // Stop early if the merged receiver has been dropped
+            if tx.send(value).is_err() {
+                break;
+            }
+        }
+    });
+
+    rx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_subscriber_gets_every_message() {
+        let mut broadcast = Broadcast::new();
+        let first = broadcast.subscribe();
+        let second = broadcast.subscribe();
+
+        let sender = thread::spawn(move || {
+            for n in 1..=3 {
+                broadcast.send(n);
+            }
+            // `broadcast` is dropped when the thread finishes, closing every channel
+        });
+        sender.join().unwrap();
+
+        assert_eq!(first.iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(second.iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn dropped_subscribers_are_removed() {
+        let mut broadcast = Broadcast::new();
+        let kept = broadcast.subscribe();
+        drop(broadcast.subscribe());
+
+        broadcast.send("hello");
+
+        assert_eq!(broadcast.senders.len(), 1);
+        assert_eq!(kept.recv(), Ok("hello"));
+    }
+
+    #[test]
+    fn merge_ordered_sorts_by_timestamp() {
+        let (tx_a, rx_a) = mpsc::channel();
+        let (tx_b, rx_b) = mpsc::channel();
+
+        let producer_a = thread::spawn(move || {
+            for time in [1, 4, 5, 9] {
+                tx_a.send((time, format!("a{time}"))).unwrap();
+                thread::sleep(Duration::from_millis(1));
+            }
+        });
+        let producer_b = thread::spawn(move || {
+            for time in [2, 3, 6, 10, 11] {
+                tx_b.send((time, format!("b{time}"))).unwrap();
+            }
+        });
+
+        let merged: Vec<String> = merge_ordered(rx_a, rx_b).iter().collect();
+        producer_a.join().unwrap();
+        producer_b.join().unwrap();
+
+        assert_eq!(
+            merged,
+            vec!["a1", "b2", "b3", "a4", "a5", "b6", "a9", "b10", "b11"]
+        );
+    }
+
+    #[test]
+    fn merge_ordered_with_one_empty_channel() {
+        let (tx_a, rx_a) = mpsc::channel::<(u64, i32)>();
+        let (tx_b, rx_b) = mpsc::channel();
+        drop(tx_a);
+        tx_b.send((7, 70)).unwrap();
+        tx_b.send((8, 80)).unwrap();
+        drop(tx_b);
+
+        assert_eq!(
+            merge_ordered(rx_a, rx_b).iter().collect::<Vec<_>>(),
+            vec![70, 80]
+        );
+    }
 }
@@ -160,10 +160,58 @@ fn main() {
     }
 
     println!("Result: {}", *counter.lock().unwrap());
+
+    // Note: not in book - see the sections after main()
+    println!("Atomic result: {}", atomic_increment(10, 1));
+
+    let numbers: Vec<u64> = (1..=100).collect();
+    let sum_of_squares = parallel_reduce(&numbers, 4, |n| n * n);
+    println!("Sum of squares: {sum_of_squares}");
+
+    let savings = Mutex::new(100);
+    let checking = Mutex::new(0);
+    {
+        let (mut from, mut to) = lock_both(&savings, &checking);
+        *from -= 25;
+        *to += 25;
+    }
+    println!(
+        "Savings: {}, Checking: {}",
+        *savings.lock().unwrap(),
+        *checking.lock().unwrap()
+    );
 }
 
 // Note that there are other atomic types provided by std::sync::atomic that provide safe, concurrent, atomic, access to primative types
 
+// Note: not in book
+// The counter above can be written with an AtomicUsize instead of a Mutex<usize>
+// fetch_add() adds to the value in a single step that other threads can't interrupt, so there is no lock to acquire (it is lock-free)
+// Ordering::Relaxed is enough here, since the threads only need the increments to not get lost, not to see each other's other writes in a particular order
+// join() makes sure every thread has finished before the final value is read
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+fn atomic_increment(threads: usize, per_thread: usize) -> usize {
+    let counter = Arc::new(AtomicUsize::new(0));
+    let mut handles = vec![];
+
+    for _ in 0..threads {
+        let counter = Arc::clone(&counter);
+        let handle = thread::spawn(move || {
+            for _ in 0..per_thread {
+                counter.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        handles.push(handle);
+    }
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    counter.load(Ordering::Relaxed)
+}
+
 //
 // Similarities Between RefCell<T>/Rc<T> and Mutex<T>/Arc<T>
 //
@@ -173,3 +221,155 @@ fn main() {
 // Compared to RefCell<T> which allows the mutation of contents inside Rc<T>, Mutex<T> allows mutation of contents inside an Arc<T>
 
 // Also compared to RefCell<T>, where there is a risk of reference cycles, Mutex<T> comes with the risk of creating deadlocks
+
+//
+// Reducing Lock Contention
+//
+
+// Note: not in book
+// In the counter example, every thread locks the mutex for every increment, so threads spend a lot of time waiting on each other
+// parallel_reduce() splits `items` into one chunk per thread, and each thread adds up `map(item)` for its chunk without any lock
+// Each thread only locks the mutex once, to add its chunk's total to the overall total
+
+// thread::scope() is used instead of thread::spawn(), since the threads borrow `items` and `map`
+// The scope waits for all of its threads to finish before returning, so the borrows can't outlive the data (no Arc<T> needed)
+use std::ops::AddAssign;
+
+fn parallel_reduce<T, R>(items: &[T], threads: usize, map: impl Fn(&T) -> R + Send + Sync) -> R
+where
+    T: Send + Sync,
+    R: Send + Default + AddAssign,
+{
+    if threads == 0 {
+        panic!("parallel_reduce needs at least 1 thread");
+    }
+
+    let total = Mutex::new(R::default());
+    // Round up, so the last chunk picks up any leftover items instead of needing an extra thread
+    // chunks() panics on a size of 0, which would happen for an empty slice
+    let chunk_size = items.len().div_ceil(threads).max(1);
+
+    thread::scope(|s| {
+        for chunk in items.chunks(chunk_size) {
+            // `move` copies these references (and `chunk`) into the thread, instead of borrowing variables that only live for one loop iteration
+            let total = &total;
+            let map = &map;
+            s.spawn(move || {
+                let mut local = R::default();
+                for item in chunk {
+                    local += map(item);
+                }
+                *total.lock().unwrap() += local;
+            });
+        }
+    });
+
+    // All of the threads have finished, so the mutex can be unwrapped to get the total out
+    total.into_inner().unwrap()
+}
+
+//
+// Avoiding Deadlocks with a Lock Order
+//
+
+// Note: not in book
+// A deadlock can happen when two threads need the same two locks, but lock them in a different order:
+// thread 1 locks `a` and waits for `b`, while thread 2 locks `b` and waits for `a`, so neither thread can continue
+// lock_both() always locks the mutex at the lower memory address first, so every thread locks the pair in the same order
+// The guards are still returned in the same order as the arguments, so callers don't need to know which one was locked first
+use std::sync::MutexGuard;
+
+fn lock_both<'a, T>(a: &'a Mutex<T>, b: &'a Mutex<T>) -> (MutexGuard<'a, T>, MutexGuard<'a, T>) {
+    // Locking the same mutex twice on one thread would deadlock (or panic), so treat it as a bug in the caller
+    if std::ptr::eq(a, b) {
+        panic!("lock_both needs two different mutexes");
+    }
+
+    // Casting the references to raw pointers lets us compare their addresses
+    if (a as *const Mutex<T>) < (b as *const Mutex<T>) {
+        let guard_a = a.lock().unwrap();
+        let guard_b = b.lock().unwrap();
+        (guard_a, guard_b)
+    } else {
+        let guard_b = b.lock().unwrap();
+        let guard_a = a.lock().unwrap();
+        (guard_a, guard_b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parallel_sum_of_squares_matches_single_thread() {
+        let numbers: Vec<u64> = (1..=1000).collect();
+        let expected: u64 = numbers.iter().map(|n| n * n).sum();
+
+        for threads in [1, 3, 8, 2000] {
+            assert_eq!(parallel_reduce(&numbers, threads, |n| n * n), expected);
+        }
+    }
+
+    #[test]
+    fn parallel_reduce_of_empty_slice() {
+        let numbers: Vec<i32> = Vec::new();
+
+        assert_eq!(parallel_reduce(&numbers, 4, |n| *n), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least 1 thread")]
+    fn parallel_reduce_rejects_zero_threads() {
+        parallel_reduce(&[1, 2, 3], 0, |n| *n);
+    }
+
+    #[test]
+    fn lock_both_in_opposite_orders_does_not_deadlock() {
+        let first = Mutex::new(0);
+        let second = Mutex::new(0);
+
+        thread::scope(|s| {
+            s.spawn(|| {
+                for _ in 0..10_000 {
+                    let (mut a, mut b) = lock_both(&first, &second);
+                    *a += 1;
+                    *b += 1;
+                }
+            });
+            s.spawn(|| {
+                for _ in 0..10_000 {
+                    // The arguments are the other way around
+                    let (mut b, mut a) = lock_both(&second, &first);
+                    *a += 1;
+                    *b += 1;
+                }
+            });
+        });
+
+        assert_eq!(*first.lock().unwrap(), 20_000);
+        assert_eq!(*second.lock().unwrap(), 20_000);
+    }
+
+    #[test]
+    fn lock_both_returns_guards_in_argument_order() {
+        let low = Mutex::new("low");
+        let high = Mutex::new("high");
+
+        let (a, b) = lock_both(&high, &low);
+        assert_eq!((*a, *b), ("high", "low"));
+    }
+
+    #[test]
+    #[should_panic(expected = "two different mutexes")]
+    fn lock_both_rejects_same_mutex() {
+        let only = Mutex::new(0);
+        let _guards = lock_both(&only, &only);
+    }
+
+    #[test]
+    fn atomic_increment_counts_every_increment() {
+        assert_eq!(atomic_increment(8, 10_000), 80_000);
+        assert_eq!(atomic_increment(0, 10), 0);
+    }
+}
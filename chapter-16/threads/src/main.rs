@@ -143,4 +143,106 @@ fn main() {
     // Note that the main thread cannot do anything with `v` after
 
     handle.join().unwrap();
+
+    // Note: not in book - see the section after main()
+    let workers = WorkerGroup::new(2);
+    for i in 1..5 {
+        workers.execute(move || println!("hi number {} from a worker", i));
+    }
+    workers.shutdown();
+}
+
+//
+// Shutting Down a Group of Worker Threads
+//
+
+// Note: not in book
+// When the main thread ends, every spawned thread is shut down, even if it was in the middle of a job
+// WorkerGroup runs jobs on a fixed number of threads, and shutdown() waits for every job that was submitted to finish
+// The workers share one receiver through an Arc<Mutex<T>> (only one worker can take each job), and the group keeps the transmitter
+
+use std::sync::{mpsc, Arc, Mutex};
+
+// A job is any closure that can be sent to another thread and run once
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+pub struct WorkerGroup {
+    sender: mpsc::Sender<Job>,
+    workers: Vec<thread::JoinHandle<()>>,
+}
+
+impl WorkerGroup {
+    pub fn new(size: usize) -> WorkerGroup {
+        // A group without any workers would never run its jobs
+        if size == 0 {
+            panic!("WorkerGroup needs at least 1 worker");
+        }
+
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let workers = (0..size)
+            .map(|_| {
+                let receiver = Arc::clone(&receiver);
+                thread::spawn(move || loop {
+                    // The lock is only held while waiting for a job, it is released at the end of this statement before the job runs
+                    let job = receiver.lock().unwrap().recv();
+                    match job {
+                        Ok(job) => job(),
+                        // recv() only returns an Err once the transmitter is dropped and every job has been taken
+                        Err(_) => break,
+                    }
+                })
+            })
+            .collect();
+
+        WorkerGroup { sender, workers }
+    }
+
+    pub fn execute<F>(&self, f: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.sender.send(Box::new(f)).unwrap();
+    }
+
+    // Takes `self`, so no more jobs can be submitted after shutting down
+    pub fn shutdown(self) {
+        // Dropping the transmitter closes the channel, which is the signal for the workers to stop once the queue is empty
+        drop(self.sender);
+
+        for worker in self.workers {
+            worker.join().unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn shutdown_waits_for_every_job() {
+        let completed = Arc::new(AtomicUsize::new(0));
+        let workers = WorkerGroup::new(3);
+
+        for _ in 0..20 {
+            let completed = Arc::clone(&completed);
+            workers.execute(move || {
+                // Slow jobs, so most are still queued when shutdown() is called
+                thread::sleep(Duration::from_millis(5));
+                completed.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        workers.shutdown();
+
+        assert_eq!(completed.load(Ordering::SeqCst), 20);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least 1 worker")]
+    fn rejects_zero_workers() {
+        WorkerGroup::new(0);
+    }
 }
@@ -52,6 +52,13 @@ fn main() {
         let futures: Vec<Pin<Box<dyn Future<Output = ()>>>> =
             vec![Box::pin(tx1_fut), Box::pin(rx_fut), Box::pin(tx_fut)];
         trpl::join_all(futures).await;
+        // Note: not in book - the helpers defined after main() do the boxing and joining without the type annotation
+        let outputs = join_many(vec![
+            boxed(async { "boxed" }),
+            boxed(async { "and joined" }),
+        ])
+        .await;
+        println!("{}", outputs.join(" "));
 
         // Since `Pin` is a wrapper type to have a single type for the Vec,
         // We can use `Pin` directly on the futures without doing a heap allocation for `Box` through the `std::pin::pin` macro
@@ -154,6 +161,20 @@ fn main() {
         trpl::race(a, b).await;
     });
 
+    // Note: not in book - see cooperative_sum() after main()
+    trpl::run(async {
+        let chunks = vec![vec![1, 2, 3], vec![4, 5], vec![6]];
+        let sum = async {
+            let total = cooperative_sum(chunks).await;
+            println!("'sum' finished with {total}.");
+        };
+        let other = async {
+            println!("'other' ran while 'sum' was still going.");
+        };
+
+        trpl::join(sum, other).await;
+    });
+
     //
     // Building out own async abstractions
     //
@@ -185,3 +206,107 @@ fn main() {
         }
     });
 }
+
+//
+// Helpers for Boxing and Joining Futures
+//
+
+// Note: not in book
+// boxed() pins a future on the heap and turns it into a trait object, so futures from different async blocks can go in the same Vec
+// It does the same thing as Box::pin(), but the return type spells out the trait object, so the Vec's type doesn't need an annotation
+// The lifetime `'a` lets the future borrow values (like `rx_fut` borrowing `rx`), a `'static` bound would only allow futures that own everything they use
+fn boxed<'a, F>(future: F) -> Pin<Box<dyn Future<Output = F::Output> + 'a>>
+where
+    F: Future + 'a,
+{
+    Box::pin(future)
+}
+
+// join_many() waits for every future, and returns their outputs in the same order as the futures in the Vec
+async fn join_many<'a, T>(futures: Vec<Pin<Box<dyn Future<Output = T> + 'a>>>) -> Vec<T> {
+    trpl::join_all(futures).await
+}
+
+//
+// Yielding Between Chunks of Work
+//
+
+// Note: not in book
+// cooperative_sum() adds up each chunk, and yields back to the runtime after each one, like `a` and `b` in the Yielding section
+// Without the yield_now() calls, the whole sum would run in one go, and any other future would have to wait until it finished (starvation)
+// Each chunk is still summed synchronously, so smaller chunks let other futures run more often (at the cost of more switching)
+async fn cooperative_sum(chunks: Vec<Vec<i32>>) -> i32 {
+    let mut total = 0;
+    for chunk in chunks {
+        total += chunk.iter().sum::<i32>();
+        trpl::yield_now().await;
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn join_many_boxed_futures() {
+        trpl::run(async {
+            let futures = vec![
+                boxed(async { 1 }),
+                // The slowest future's output is still second in the Vec
+                boxed(async {
+                    trpl::sleep(Duration::from_millis(10)).await;
+                    2
+                }),
+                boxed(async { 3 }),
+            ];
+
+            assert_eq!(join_many(futures).await, vec![1, 2, 3]);
+        });
+    }
+
+    #[test]
+    fn boxed_futures_can_borrow() {
+        let names = [String::from("a"), String::from("b")];
+
+        let lengths = trpl::run(async {
+            let futures = names
+                .iter()
+                .map(|name| boxed(async move { name.len() }))
+                .collect();
+            join_many(futures).await
+        });
+
+        assert_eq!(lengths, vec![1, 1]);
+    }
+
+    #[test]
+    fn cooperative_sum_lets_other_futures_run() {
+        use std::cell::Cell;
+
+        let sum_finished = Cell::new(false);
+        let fast_ran_first = Cell::new(false);
+
+        trpl::run(async {
+            let chunks = (1..=100).map(|n| vec![n; 10]).collect();
+            let sum = async {
+                assert_eq!(cooperative_sum(chunks).await, 50_500);
+                sum_finished.set(true);
+            };
+            // join() polls `sum` first, so `fast` only gets a turn before the sum is done if the sum yields
+            let fast = async {
+                fast_ran_first.set(!sum_finished.get());
+            };
+
+            trpl::join(sum, fast).await;
+        });
+
+        assert!(sum_finished.get());
+        assert!(fast_ran_first.get());
+    }
+
+    #[test]
+    fn cooperative_sum_of_nothing() {
+        assert_eq!(trpl::run(cooperative_sum(Vec::new())), 0);
+    }
+}
@@ -150,4 +150,128 @@ fn main() {
             }
         }
     });
+
+    // Note: not in book - see debounce() after main()
+    trpl::run(async {
+        // Typing "rust" one letter at a time only searches once, for the whole word
+        let keystrokes = trpl::stream_from_iter(["r", "ru", "rus", "rust"]);
+        let mut searches = pin!(debounce(keystrokes, Duration::from_millis(100)));
+        while let Some(query) = searches.next().await {
+            println!("Searching for '{query}'");
+        }
+    });
+
+    // Note: not in book - see collect_stream() after main()
+    trpl::run(async {
+        let doubled = trpl::stream_from_iter(1..=5).map(|n| n * 2);
+        println!("Collected: {:?}", collect_stream(doubled).await);
+    });
+}
+
+//
+// Debouncing a Stream
+//
+
+// Note: not in book
+// throttle() limits how often items are let through, debounce() instead waits for the stream to go quiet
+// An item is only sent on once no other item has arrived for `quiet`, so each burst of items becomes its last item
+// e.g. for search-as-you-type, only search once the user stops typing, instead of for every letter
+
+// Like get_messages(), a task is spawned to do the waiting, and the items are sent through a channel
+// The input stream is moved into the spawned task, so it needs to be `Send + 'static` (the same goes for its items)
+fn debounce<S>(stream: S, quiet: Duration) -> impl Stream<Item = S::Item>
+where
+    S: Stream + Send + 'static,
+    S::Item: Send + 'static,
+{
+    let (tx, rx) = trpl::channel();
+
+    trpl::spawn_task(async move {
+        // timeout() gives an Err once no item has arrived for `quiet` after the last one, which means the burst is over
+        let mut items = pin!(stream.timeout(quiet));
+        // The latest item from the current burst, which hasn't been sent yet
+        let mut latest = None;
+
+        while let Some(result) = items.next().await {
+            match result {
+                Ok(item) => latest = Some(item),
+                Err(_) => {
+                    if let Some(item) = latest.take() {
+                        // Stop if the debounced stream has been dropped
+                        if tx.send(item).is_err() {
+                            return;
+                        }
+                    }
+                }
+            }
+        }
+
+        // The input stream ended, so nothing else can arrive after the last item
+        if let Some(item) = latest {
+            let _ = tx.send(item);
+        }
+    });
+
+    ReceiverStream::new(rx)
+}
+
+//
+// Collecting a Stream
+//
+
+// Note: not in book
+// collect_stream() waits for every item in the stream, and returns them in a Vec (like collect() on an iterator)
+// next() needs the stream to be pinned, so the stream is pinned inside the function instead of making callers do it
+async fn collect_stream<S, T>(stream: S) -> Vec<T>
+where
+    S: Stream<Item = T>,
+{
+    let mut stream = pin!(stream);
+    let mut items = Vec::new();
+    while let Some(item) = stream.next().await {
+        items.push(item);
+    }
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debounce_keeps_last_item_of_each_burst() {
+        let received = trpl::run(async {
+            let (tx, rx) = trpl::channel();
+            trpl::spawn_task(async move {
+                let bursts = [vec![1, 2, 3], vec![4, 5], vec![6]];
+                for burst in bursts {
+                    for n in burst {
+                        tx.send(n).unwrap();
+                        trpl::sleep(Duration::from_millis(1)).await;
+                    }
+                    // Much longer than the quiet period, so each burst is debounced on its own
+                    trpl::sleep(Duration::from_millis(200)).await;
+                }
+            });
+
+            collect_stream(debounce(ReceiverStream::new(rx), Duration::from_millis(50))).await
+        });
+
+        assert_eq!(received, vec![3, 5, 6]);
+    }
+
+    #[test]
+    fn collect_stream_from_iter() {
+        let values = [1, 2, 3, 4, 5];
+        let stream = trpl::stream_from_iter(values.iter().map(|n| n * 2));
+
+        assert_eq!(trpl::run(collect_stream(stream)), vec![2, 4, 6, 8, 10]);
+    }
+
+    #[test]
+    fn collect_empty_stream() {
+        let stream = trpl::stream_from_iter(Vec::<String>::new());
+
+        assert!(trpl::run(collect_stream(stream)).is_empty());
+    }
 }
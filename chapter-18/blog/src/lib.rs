@@ -3,11 +3,20 @@
 // It is recommended to go through the chapter itself instead
 //
 
+use std::time::SystemTime;
+
+// Note: not in book - a reusable version of the state pattern, see state_machine.rs
+pub mod state_machine;
+
 // A public `Post` struct
 pub struct Post {
     // Post will hold a trait object of Box<dyn State> inside an Option<T> in a private `state` field
     state: Option<Box<dyn State>>,
     content: String,
+    // Note: not in book - the number of words the content needs before a review can be requested
+    min_words: usize,
+    // Note: not in book - when the post moved into each state, and the name of that state
+    history: Vec<(SystemTime, &'static str)>,
 }
 
 impl Post {
@@ -18,6 +27,18 @@ impl Post {
         Post {
             state: Some(Box::new(Draft {})),
             content: String::new(),
+            min_words: 0,
+            history: Vec::new(),
+        }
+    }
+
+    // Note: not in book
+    // Creates a Post that stays a draft when request_review() is called, until it has at least `min_words` words
+    // Post::new() doesn't have a minimum, so even an empty draft can be sent for review
+    pub fn with_min_words(min_words: usize) -> Post {
+        Post {
+            min_words,
+            ..Post::new()
         }
     }
 
@@ -39,6 +60,18 @@ impl Post {
         self.state.as_ref().unwrap().content(self)
     }
 
+    // Note: not in book
+    // Returns the first `max` characters of the content, which is also "" unless the post is published since it uses content()
+    // Slicing at byte `max` could panic in the middle of a multi-byte character, so the slice ends at the start of the (max + 1)th character instead
+    pub fn summary(&self, max: usize) -> &str {
+        let content = self.content();
+        match content.char_indices().nth(max) {
+            Some((end, _)) => &content[..end],
+            // The content has `max` characters or fewer
+            None => content,
+        }
+    }
+
     // Public method to transition a Post to the PendingReview state
     pub fn request_review(&mut self) {
         // If there is a state object, call the request_review() function on the state object
@@ -49,15 +82,34 @@ impl Post {
             // Note that Rust doesn't allow unpopulated fields in structs
             // We need to set `state` to `None` temporarily rather than setting it directly (as if there was no Option<T>) to get ownership of the `state` value
             // This ensures `Post` can't use the old `state` value after transforming it into a new state
-            self.state = Some(s.request_review())
+            // Note: not in book - the post is passed in, so the Draft state can check its word count
+            // `state` has already been taken out of the post, so the immutable borrow of `self` doesn't overlap with anything
+            self.state = Some(s.request_review(self));
+            // Note: not in book - see record_history()
+            self.record_history();
         }
     }
 
     pub fn approve(&mut self) {
         if let Some(s) = self.state.take() {
-            self.state = Some(s.approve())
+            self.state = Some(s.approve());
+            // Note: not in book - see record_history()
+            self.record_history();
         }
     }
+
+    // Note: not in book
+    // An audit log of every request_review() and approve() call, oldest first, with the state the post was in after the call
+    pub fn history(&self) -> &[(SystemTime, &'static str)] {
+        &self.history
+    }
+
+    // Calls that leave the post in the same state (e.g. approving a draft) are recorded too, so the log shows every attempt
+    // e.g. a draft that was approved before it was reviewed has two "Draft" entries in a row
+    fn record_history(&mut self) {
+        let after = self.state.as_ref().unwrap().name();
+        self.history.push((SystemTime::now(), after));
+    }
 }
 
 // Private `State` trait that define the behaviour that all state objects for a `Post` must have
@@ -65,7 +117,7 @@ trait State {
     // Internal request_review() method for State objects
     // Note that `self` is a Box<Self> rather than a `self`, `&self` or `&mut self`, which means the method is only valid on a Box holding the type
     // This syntax takes ownership of the Box<Self>, to invalidate the old state
-    fn request_review(self: Box<Self>) -> Box<dyn State>;
+    fn request_review(self: Box<Self>, post: &Post) -> Box<dyn State>;
     // Internal approve() method for State objects
     // Similar to the above request_review() function
     fn approve(self: Box<Self>) -> Box<dyn State>;
@@ -73,14 +125,23 @@ trait State {
     fn content<'a>(&self, post: &'a Post) -> &'a str {
         ""
     }
+    // Note: not in book - the name of the state, for Post's history
+    fn name(&self) -> &'static str;
 }
 
 // Draft state struct
 struct Draft {}
 
 impl State for Draft {
+    fn name(&self) -> &'static str {
+        "Draft"
+    }
     // Return a new, boxed instance of a PendingReview struct
-    fn request_review(self: Box<Self>) -> Box<dyn State> {
+    // Note: not in book - unless the post is too short, then it stays a draft
+    fn request_review(self: Box<Self>, post: &Post) -> Box<dyn State> {
+        if post.content.split_whitespace().count() < post.min_words {
+            return self;
+        }
         Box::new(PendingReview {})
     }
     // No effect
@@ -92,8 +153,11 @@ impl State for Draft {
 struct PendingReview {}
 
 impl State for PendingReview {
+    fn name(&self) -> &'static str {
+        "PendingReview"
+    }
     // Return the same state object, since the post should stay in the PendingReview state if requested a review
-    fn request_review(self: Box<Self>) -> Box<dyn State> {
+    fn request_review(self: Box<Self>, _post: &Post) -> Box<dyn State> {
         self
     }
     // Return a new boxed instance of a Published struct
@@ -105,8 +169,11 @@ impl State for PendingReview {
 struct Published {}
 
 impl State for Published {
+    fn name(&self) -> &'static str {
+        "Published"
+    }
     // No effect
-    fn request_review(self: Box<Self>) -> Box<dyn State> {
+    fn request_review(self: Box<Self>, _post: &Post) -> Box<dyn State> {
         self
     }
     // No effect
@@ -120,3 +187,102 @@ impl State for Published {
         &post.content
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Only published posts return their content, so this checks which state the post ended up in
+    fn publish(post: &mut Post) -> &str {
+        post.request_review();
+        post.approve();
+        post.content()
+    }
+
+    #[test]
+    fn short_draft_stays_a_draft() {
+        let mut post = Post::with_min_words(5);
+        post.add_text("Too short to review");
+
+        assert_eq!(publish(&mut post), "");
+
+        // Adding more text lets the review go ahead
+        post.add_text(" until now");
+        assert_eq!(publish(&mut post), "Too short to review until now");
+    }
+
+    #[test]
+    fn long_enough_draft_is_reviewed() {
+        let mut post = Post::with_min_words(3);
+        post.add_text("Exactly three words");
+
+        assert_eq!(publish(&mut post), "Exactly three words");
+    }
+
+    #[test]
+    fn empty_draft_without_minimum() {
+        let mut post = Post::new();
+
+        post.request_review();
+        post.approve();
+        // The post is published, even though it has no content
+        assert_eq!(post.content(), "");
+        post.add_text("Added after publishing");
+        assert_eq!(post.content(), "Added after publishing");
+    }
+
+    #[test]
+    fn summary_only_when_published() {
+        let mut post = Post::new();
+        post.add_text("I ate a salad for lunch today");
+        assert_eq!(post.summary(9), "");
+
+        post.request_review();
+        assert_eq!(post.summary(9), "");
+
+        post.approve();
+        assert_eq!(post.summary(9), "I ate a s");
+        assert_eq!(post.summary(100), "I ate a salad for lunch today");
+    }
+
+    #[test]
+    fn summary_of_multi_byte_content() {
+        let mut post = Post::new();
+        post.add_text("Здравствуйте");
+
+        assert_eq!(publish(&mut post), "Здравствуйте");
+        // Each character is 2 bytes, so slicing at byte 5 would panic
+        assert_eq!(post.summary(5), "Здрав");
+        assert_eq!(post.summary(0), "");
+    }
+
+    #[test]
+    fn history_records_each_transition() {
+        let mut post = Post::new();
+        post.add_text("I ate a salad for lunch today");
+        assert!(post.history().is_empty());
+
+        // Approving a draft doesn't change its state, but the attempt is still recorded
+        post.approve();
+        post.request_review();
+        post.request_review();
+        post.approve();
+        // Published posts can't go back to review
+        post.request_review();
+
+        let labels: Vec<&str> = post.history().iter().map(|(_, label)| *label).collect();
+        assert_eq!(
+            labels,
+            vec![
+                "Draft",
+                "PendingReview",
+                "PendingReview",
+                "Published",
+                "Published"
+            ]
+        );
+
+        // The timestamps are in order
+        assert!(post.history().windows(2).all(|pair| pair[0].0 <= pair[1].0));
+    }
+}
@@ -0,0 +1,182 @@
+// Note: not in book
+// The `State` trait in lib.rs only works for posts: its methods are the post's actions, and content() takes a `Post`
+// This module pulls out the parts that every state machine needs, so other state machines can reuse them
+
+// Each state machine has its own set of events (e.g. requesting a review, or a timer running out),
+// so the events are an associated type rather than one method per event like in `State`
+// transition() takes `self`, so the old state is consumed and can't be used after the transition (the same reason `State` takes `self: Box<Self>`)
+// Events that don't apply to the current state should return `self`, so they have no effect
+pub trait StateMachine {
+    type Event;
+
+    fn transition(self, event: Self::Event) -> Self;
+}
+
+// Machine holds the current state, and does the Option<T> and take() steps from Post::request_review() for any StateMachine
+pub struct Machine<S: StateMachine> {
+    state: Option<S>,
+}
+
+impl<S> Machine<S>
+where
+    S: StateMachine,
+{
+    pub fn new(initial: S) -> Machine<S> {
+        Machine {
+            state: Some(initial),
+        }
+    }
+
+    pub fn fire(&mut self, event: S::Event) {
+        if let Some(s) = self.state.take() {
+            self.state = Some(s.transition(event));
+        }
+    }
+
+    // `state` is only None while fire() is running, so unwrap() can't panic here
+    pub fn state(&self) -> &S {
+        self.state.as_ref().unwrap()
+    }
+}
+
+//
+// The Blog Post Workflow as a StateMachine
+//
+
+// The states are an enum instead of seperate structs, since the machine only needs to know which state it is in
+#[derive(Debug, PartialEq)]
+pub enum PostState {
+    Draft,
+    PendingReview,
+    Published,
+}
+
+pub enum PostEvent {
+    RequestReview,
+    Approve,
+}
+
+impl StateMachine for PostState {
+    type Event = PostEvent;
+
+    fn transition(self, event: PostEvent) -> PostState {
+        match (self, event) {
+            (PostState::Draft, PostEvent::RequestReview) => PostState::PendingReview,
+            (PostState::PendingReview, PostEvent::Approve) => PostState::Published,
+            // Any other event has no effect
+            (state, _) => state,
+        }
+    }
+}
+
+// The same public API as the `Post` in lib.rs, built on a Machine
+pub struct Post {
+    machine: Machine<PostState>,
+    content: String,
+}
+
+impl Post {
+    pub fn new() -> Post {
+        Post {
+            machine: Machine::new(PostState::Draft),
+            content: String::new(),
+        }
+    }
+
+    pub fn add_text(&mut self, text: &str) {
+        self.content.push_str(text);
+    }
+
+    pub fn content(&self) -> &str {
+        match self.machine.state() {
+            PostState::Published => &self.content,
+            _ => "",
+        }
+    }
+
+    pub fn request_review(&mut self) {
+        self.machine.fire(PostEvent::RequestReview);
+    }
+
+    pub fn approve(&mut self) {
+        self.machine.fire(PostEvent::Approve);
+    }
+}
+
+impl Default for Post {
+    fn default() -> Self {
+        Post::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn post_workflow() {
+        let mut post = Post::new();
+        post.add_text("I ate a salad for lunch today");
+
+        // Approving a draft has no effect
+        post.approve();
+        assert_eq!(post.content(), "");
+
+        post.request_review();
+        assert_eq!(post.content(), "");
+
+        post.approve();
+        assert_eq!(post.content(), "I ate a salad for lunch today");
+    }
+
+    // A second state machine that reuses Machine
+    #[derive(Debug, PartialEq)]
+    enum TrafficLight {
+        Red,
+        Green,
+        Yellow,
+    }
+
+    enum LightEvent {
+        Timer,
+        Emergency,
+    }
+
+    impl StateMachine for TrafficLight {
+        type Event = LightEvent;
+
+        fn transition(self, event: LightEvent) -> TrafficLight {
+            match (self, event) {
+                (_, LightEvent::Emergency) => TrafficLight::Red,
+                (TrafficLight::Red, LightEvent::Timer) => TrafficLight::Green,
+                (TrafficLight::Green, LightEvent::Timer) => TrafficLight::Yellow,
+                (TrafficLight::Yellow, LightEvent::Timer) => TrafficLight::Red,
+            }
+        }
+    }
+
+    #[test]
+    fn traffic_light_cycles() {
+        let mut light = Machine::new(TrafficLight::Red);
+
+        light.fire(LightEvent::Timer);
+        assert_eq!(light.state(), &TrafficLight::Green);
+        light.fire(LightEvent::Timer);
+        assert_eq!(light.state(), &TrafficLight::Yellow);
+        light.fire(LightEvent::Timer);
+        assert_eq!(light.state(), &TrafficLight::Red);
+    }
+
+    #[test]
+    fn traffic_light_emergency() {
+        let mut light = Machine::new(TrafficLight::Red);
+        light.fire(LightEvent::Timer);
+
+        light.fire(LightEvent::Emergency);
+        assert_eq!(light.state(), &TrafficLight::Red);
+
+        // Stays red during an emergency
+        light.fire(LightEvent::Emergency);
+        assert_eq!(light.state(), &TrafficLight::Red);
+    }
+}
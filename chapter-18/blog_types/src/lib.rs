@@ -26,6 +26,16 @@ impl Post {
 
 // Note that DraftPost doesn't have a content() method, so any attempt to call content() on them will result in a compiler error
 impl DraftPost {
+    // Note: not in book
+    // Creates a draft that already has `s` as its content, instead of calling Post::new() and then add_text()
+    // Clippy suggests implementing the FromStr trait instead, but FromStr::from_str() returns a Result, and creating a draft can't fail
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> DraftPost {
+        DraftPost {
+            content: String::from(s),
+        }
+    }
+
     // Add text to the `content` field
     pub fn add_text(&mut self, text: &str) {
         self.content.push_str(text);
@@ -53,4 +63,74 @@ impl PendingReviewPost {
             content: self.content,
         }
     }
+
+    // Note: not in book
+    // Like approve(), but a post with no content isn't approved
+    // approve() consumes the PendingReviewPost, so on failure it is handed back inside the Err instead of being dropped
+    pub fn try_approve(self) -> Result<Post, PendingReviewPost> {
+        if self.content.is_empty() {
+            return Err(self);
+        }
+        Ok(self.approve())
+    }
+}
+
+// Note: not in book
+// Takes every draft through request_review() and approve()
+// Each call consumes the post and returns the next type, so each draft is moved into the closure and only the Post comes out
+pub fn publish_all(drafts: Vec<DraftPost>) -> Vec<Post> {
+    drafts
+        .into_iter()
+        .map(|draft| draft.request_review().approve())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn draft_from_str_through_to_published() {
+        let mut draft = DraftPost::from_str("I ate a salad");
+        draft.add_text(" for lunch today");
+
+        let post = draft.request_review().approve();
+
+        assert_eq!(post.content(), "I ate a salad for lunch today");
+    }
+
+    #[test]
+    fn publish_all_keeps_contents_in_order() {
+        let drafts = vec![
+            DraftPost::from_str("First"),
+            DraftPost::from_str("Second"),
+            Post::new(),
+        ];
+
+        let posts = publish_all(drafts);
+
+        let contents: Vec<&str> = posts.iter().map(|post| post.content()).collect();
+        assert_eq!(contents, vec!["First", "Second", ""]);
+    }
+
+    #[test]
+    fn try_approve_empty_post_gives_it_back() {
+        let pending = Post::new().request_review();
+
+        match pending.try_approve() {
+            Ok(_) => panic!("an empty post shouldn't be approved"),
+            // The pending post can still be used after the failed approval
+            Err(pending) => assert_eq!(pending.approve().content(), ""),
+        }
+    }
+
+    #[test]
+    fn try_approve_post_with_content() {
+        let pending = DraftPost::from_str("I ate a salad for lunch today").request_review();
+
+        match pending.try_approve() {
+            Ok(post) => assert_eq!(post.content(), "I ate a salad for lunch today"),
+            Err(_) => panic!("a post with content should be approved"),
+        }
+    }
 }
@@ -27,9 +27,35 @@
 // However, trait objects differ from traditional objects in that we can't add data to a trait object.
 // The specific purpose of trait objects is to allow abstraction across common behaviour
 
+// Note: not in book - draw() writes to `out` instead of calling println!() directly,
+// so the output can be sent to stdout in main() or captured in a buffer in tests
+// `out` has to be a `&mut dyn Write` rather than `&mut impl Write`, since a trait with generic methods can't be made into a trait object
+use std::io::Write;
+
 // Defining a trait: `Draw` with a draw() method
 pub trait Draw {
-    fn draw(&self);
+    fn draw(&self, out: &mut dyn Write);
+
+    // Note: not in book
+    // The (width, height) of the component, used by Screen to work out how much space it needs
+    fn size(&self) -> (u32, u32);
+
+    // Note: not in book
+    // Components are visible by default, so types like `SelectBox` don't need to implement visible() to be drawn
+    fn visible(&self) -> bool {
+        true
+    }
+
+    // Note: not in book
+    // accept() calls the method on the Visitor for this component's concrete type (see the Visitor section below)
+    // The default does nothing, so components that a Visitor doesn't know about are skipped
+    fn accept(&self, _visitor: &mut dyn Visitor) {}
+
+    // Note: not in book
+    // The name given to the component by Screen::add_named(), components are unnamed by default
+    fn name(&self) -> Option<&str> {
+        None
+    }
 }
 
 // A `Screen` struct which holds a `components` vector that is of type `Box<dyn Draw>`, which is a trait object
@@ -40,10 +66,87 @@ pub struct Screen {
 
 // Define a run() method which will call the draw() method on each item in `components`
 impl Screen {
-    pub fn run(&self) {
-        for component in self.components.iter() {
-            component.draw();
+    // Note: not in book - the same as a struct literal, but shorter to write
+    pub fn new(components: Vec<Box<dyn Draw>>) -> Screen {
+        Screen { components }
+    }
+
+    pub fn run(&self, out: &mut dyn Write) {
+        // Note: not in book - hidden components stay in `components`, but aren't drawn
+        for component in self.components.iter().filter(|c| c.visible()) {
+            component.draw(out);
+        }
+    }
+
+    // Note: not in book
+    // add_named() adds a component that can be looked up by name later on
+    // The name is stored with the component (see `Named` below), so it stays with the component however `components` is changed
+    // (e.g. after a remove(), an insert() at the front, or a clear())
+    pub fn add_named(&mut self, name: String, component: Box<dyn Draw>) {
+        self.components.push(Box::new(Named {
+            name,
+            inner: component,
+        }));
+    }
+
+    // Draws only the first component with the given name, returning None if there is no component with that name
+    pub fn draw_named(&self, name: &str, out: &mut dyn Write) -> Option<()> {
+        let component = self.components.iter().find(|c| c.name() == Some(name))?;
+        component.draw(out);
+        Some(())
+    }
+
+    // Note: not in book
+    // Returns the (width, height) needed to fit every component, assuming each one is drawn starting at the top-left corner of the screen
+    // Hidden components are included, so the screen doesn't change size when they are shown again
+    pub fn total_bounds(&self) -> (u32, u32) {
+        self.components
+            .iter()
+            .map(|component| component.size())
+            .fold((0, 0), |(width, height), (w, h)| {
+                (width.max(w), height.max(h))
+            })
+    }
+
+    // Note: not in book
+    // Removes and returns the component at `index`, shifting the components after it to the left (the same as Vec::remove())
+    // Unlike Vec::remove(), an index that is out of bounds returns None instead of panicking
+    pub fn remove(&mut self, index: usize) -> Option<Box<dyn Draw>> {
+        if index >= self.components.len() {
+            return None;
         }
+        Some(self.components.remove(index))
+    }
+}
+
+// Note: not in book
+// Named wraps a component from add_named() to give it a name, and passes every other Draw method on to the component
+// It's a Draw itself, so it can go into `components` with all of the unnamed components
+struct Named {
+    name: String,
+    inner: Box<dyn Draw>,
+}
+
+impl Draw for Named {
+    fn draw(&self, out: &mut dyn Write) {
+        self.inner.draw(out);
+    }
+
+    fn size(&self) -> (u32, u32) {
+        self.inner.size()
+    }
+
+    fn visible(&self) -> bool {
+        self.inner.visible()
+    }
+
+    // The visitor still gets the concrete type of the wrapped component
+    fn accept(&self, visitor: &mut dyn Visitor) {
+        self.inner.accept(visitor);
+    }
+
+    fn name(&self) -> Option<&str> {
+        Some(&self.name)
     }
 }
 
@@ -84,11 +187,25 @@ pub struct Button {
     pub width: u32,
     pub height: u32,
     pub label: String,
+    // Note: not in book - set to false to hide the button without removing it from the screen
+    pub visible: bool,
 }
 
 impl Draw for Button {
-    fn draw(&self) {
-        println!("Drawing a Button");
+    fn draw(&self, out: &mut dyn Write) {
+        writeln!(out, "Drawing a Button").unwrap();
+    }
+
+    fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn visible(&self) -> bool {
+        self.visible
+    }
+
+    fn accept(&self, visitor: &mut dyn Visitor) {
+        visitor.visit_button(self);
     }
 }
 
@@ -96,3 +213,247 @@ impl Draw for Button {
 // (e.g. `TextField` might might have the same fields, plus a `placeholder` field)
 // Each type that will be drawn will implement the `Draw` trait but use different code in the draw() method to define how to draw that particular type
 // The `Button` type might have another `impl` block to define additional methods that won't apply to other types
+
+//
+// Visiting Trait Objects
+//
+
+// Note: not in book
+// Since a trait object can't be downcast to its concrete type, Screen can't check whether a component is a `Button` or a `SelectBox`
+// Instead, each component calls the Visitor method for its own type in accept(), so the visitor gets the concrete type without needing `Any`
+// This needs the library to know every type a Visitor can handle, so `SelectBox` has been moved here from main.rs
+
+pub trait Visitor {
+    fn visit_button(&mut self, button: &Button);
+    fn visit_select_box(&mut self, select_box: &SelectBox);
+}
+
+pub struct SelectBox {
+    pub width: u32,
+    pub height: u32,
+    pub options: Vec<String>,
+}
+
+impl Draw for SelectBox {
+    fn draw(&self, out: &mut dyn Write) {
+        writeln!(out, "Drawing a SelectBox").unwrap();
+    }
+
+    fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn accept(&self, visitor: &mut dyn Visitor) {
+        visitor.visit_select_box(self);
+    }
+}
+
+impl Screen {
+    // Passes every component (including hidden ones) to the visitor
+    pub fn visit(&self, visitor: &mut dyn Visitor) {
+        for component in self.components.iter() {
+            component.accept(visitor);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn button(label: &str) -> Box<dyn Draw> {
+        Box::new(Button {
+            width: 50,
+            height: 10,
+            label: String::from(label),
+            visible: true,
+        })
+    }
+
+    // A component that draws its own text, so tests can tell components apart in the output
+    struct Text(&'static str);
+
+    impl Draw for Text {
+        fn draw(&self, out: &mut dyn Write) {
+            writeln!(out, "{}", self.0).unwrap();
+        }
+
+        fn size(&self) -> (u32, u32) {
+            (self.0.len() as u32, 1)
+        }
+    }
+
+    fn drawn(screen: &Screen) -> String {
+        let mut buffer = Vec::new();
+        screen.run(&mut buffer);
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn draws_named_button() {
+        let mut screen = Screen::new(vec![button("Unnamed")]);
+        screen.add_named(String::from("ok"), button("OK"));
+
+        let mut buffer = Vec::new();
+        assert_eq!(screen.draw_named("ok", &mut buffer), Some(()));
+        // Only the named button is drawn, not the whole screen
+        assert_eq!(String::from_utf8(buffer).unwrap(), "Drawing a Button\n");
+    }
+
+    #[test]
+    fn draw_named_misses_unknown_name() {
+        let mut screen = Screen::new(Vec::new());
+        screen.add_named(String::from("ok"), button("OK"));
+
+        let mut buffer = Vec::new();
+        assert_eq!(screen.draw_named("cancel", &mut buffer), None);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn remove_middle_component() {
+        let mut screen = Screen::new(vec![
+            Box::new(Text("first")),
+            Box::new(Text("second")),
+            Box::new(Text("third")),
+        ]);
+
+        let mut removed = Vec::new();
+        screen.remove(1).unwrap().draw(&mut removed);
+        assert_eq!(String::from_utf8(removed).unwrap(), "second\n");
+
+        assert_eq!(drawn(&screen), "first\nthird\n");
+    }
+
+    #[test]
+    fn remove_out_of_bounds() {
+        let mut screen = Screen::new(vec![Box::new(Text("only"))]);
+
+        assert!(screen.remove(1).is_none());
+        assert_eq!(drawn(&screen), "only\n");
+    }
+
+    #[test]
+    fn remove_keeps_names_in_line() {
+        let mut screen = Screen::new(Vec::new());
+        screen.add_named(String::from("a"), Box::new(Text("a")));
+        screen.add_named(String::from("b"), Box::new(Text("b")));
+        screen.remove(0);
+
+        let mut buffer = Vec::new();
+        assert_eq!(screen.draw_named("a", &mut buffer), None);
+        assert_eq!(screen.draw_named("b", &mut buffer), Some(()));
+        assert_eq!(String::from_utf8(buffer).unwrap(), "b\n");
+    }
+
+    #[test]
+    fn names_follow_components_changed_directly() {
+        let mut screen = Screen::new(Vec::new());
+        screen.add_named(String::from("a"), Box::new(Text("a")));
+        screen.add_named(String::from("b"), Box::new(Text("b")));
+
+        // `components` is pub, so it can be changed without going through Screen's methods
+        screen.components.insert(0, Box::new(Text("unnamed")));
+        screen.components.retain(|c| c.name() != Some("a"));
+
+        let mut buffer = Vec::new();
+        assert_eq!(screen.draw_named("a", &mut buffer), None);
+        assert_eq!(screen.draw_named("b", &mut buffer), Some(()));
+        assert_eq!(String::from_utf8(buffer).unwrap(), "b\n");
+
+        screen.components.clear();
+        assert_eq!(screen.draw_named("b", &mut Vec::new()), None);
+    }
+
+    #[test]
+    fn named_components_are_still_visited() {
+        let mut screen = Screen::new(Vec::new());
+        screen.add_named(String::from("ok"), button("OK"));
+
+        let mut counter = Counter::default();
+        screen.visit(&mut counter);
+        assert_eq!(counter.buttons, 1);
+    }
+
+    #[test]
+    fn run_skips_hidden_components() {
+        let screen = Screen::new(vec![
+            Box::new(Button {
+                width: 50,
+                height: 10,
+                label: String::from("Hidden"),
+                visible: false,
+            }),
+            Box::new(Button {
+                width: 50,
+                height: 10,
+                label: String::from("Shown"),
+                visible: true,
+            }),
+        ]);
+
+        assert_eq!(drawn(&screen), "Drawing a Button\n");
+    }
+
+    #[test]
+    fn total_bounds_fits_every_component() {
+        let screen = Screen::new(vec![
+            Box::new(Button {
+                width: 50,
+                height: 10,
+                label: String::from("Wide"),
+                visible: true,
+            }),
+            Box::new(Button {
+                width: 20,
+                height: 30,
+                label: String::from("Tall"),
+                visible: false,
+            }),
+        ]);
+
+        assert_eq!(screen.total_bounds(), (50, 30));
+    }
+
+    #[test]
+    fn empty_screen_has_no_bounds() {
+        assert_eq!(Screen::new(Vec::new()).total_bounds(), (0, 0));
+    }
+
+    #[derive(Default)]
+    struct Counter {
+        buttons: usize,
+        select_boxes: usize,
+    }
+
+    impl Visitor for Counter {
+        fn visit_button(&mut self, _button: &Button) {
+            self.buttons += 1;
+        }
+
+        fn visit_select_box(&mut self, _select_box: &SelectBox) {
+            self.select_boxes += 1;
+        }
+    }
+
+    #[test]
+    fn visitor_counts_component_types() {
+        let screen = Screen::new(vec![
+            button("OK"),
+            Box::new(SelectBox {
+                width: 75,
+                height: 10,
+                options: vec![String::from("Yes"), String::from("No")],
+            }),
+            button("Cancel"),
+            // Components without an accept() are skipped
+            Box::new(Text("not counted")),
+        ]);
+
+        let mut counter = Counter::default();
+        screen.visit(&mut counter);
+
+        assert_eq!(counter.buttons, 2);
+        assert_eq!(counter.select_boxes, 1);
+    }
+}
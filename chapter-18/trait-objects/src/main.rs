@@ -3,7 +3,11 @@
 //
 
 // Someone using the library can define a custom type (`SelectBox`), which will implement the `Draw` trait
+// Note: not in book - `SelectBox` now lives in lib.rs so the library's Visitor trait can refer to it,
+// but it would look like this if it was defined here
+/*
 use gui::Draw;
+use std::io::Write;
 
 struct SelectBox {
     width: u32,
@@ -12,10 +16,16 @@ struct SelectBox {
 }
 
 impl Draw for SelectBox {
-    fn draw(&self) {
-        println!("Drawing a SelectBox");
+    fn draw(&self, out: &mut dyn Write) {
+        writeln!(out, "Drawing a SelectBox").unwrap();
+    }
+
+    fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
     }
 }
+*/
+use gui::{Draw, SelectBox};
 
 //
 // Using the trait
@@ -87,6 +97,8 @@ fn main() {
 // A downside of using trait objects is how they interact with type inference
 
 // Eg: Vec<T> type inference
+use gui::{Button, Screen};
+
 fn main() {
     // An empty vector will cause a type inference error:
     /*
@@ -112,6 +124,7 @@ fn main() {
             width: 50,
             height: 10,
             label: String::from("OK"),
+            visible: true,
         }),
     ];
      */
@@ -131,6 +144,7 @@ fn main() {
             width: 50,
             height: 10,
             label: String::from("OK"),
+            visible: true,
         }),
     ];
     // or with a type annotation
@@ -148,10 +162,11 @@ fn main() {
             width: 50,
             height: 10,
             label: String::from("OK"),
+            visible: true,
         }),
     ];
-    let screen = Screen { components };
-    screen.run();
+    let screen = Screen::new(components);
+    screen.run(&mut std::io::stdout());
 }
 
 //
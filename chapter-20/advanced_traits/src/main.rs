@@ -148,51 +148,72 @@ impl Add<Meters> for Millimeters {
 
 // We need to tell Rust which method to use in the above cases
 
+// Note: not in book - fly() writes to `out` instead of calling println!() directly,
+// so the output can be sent to stdout in main() or captured in a buffer in tests
+use std::io::Write;
+
 trait Pilot {
-    fn fly(&self);
+    fn fly(&self, out: &mut impl Write);
 }
 
 trait Wizard {
-    fn fly(&self);
+    fn fly(&self, out: &mut impl Write);
 }
 
 struct Human;
 
 impl Pilot for Human {
-    fn fly(&self) {
-        println!("This is your captain speaking.");
+    fn fly(&self, out: &mut impl Write) {
+        writeln!(out, "This is your captain speaking.").unwrap();
     }
 }
 
 impl Wizard for Human {
-    fn fly(&self) {
-        println!("Up!");
+    fn fly(&self, out: &mut impl Write) {
+        writeln!(out, "Up!").unwrap();
     }
 }
 
 impl Human {
-    fn fly(&self) {
-        println!("*waving arms furiously*");
+    fn fly(&self, out: &mut impl Write) {
+        writeln!(out, "*waving arms furiously*").unwrap();
     }
 }
 
 /*
 fn main() {
+    let mut stdout = std::io::stdout();
+
     let person = Human;
     // The compiler will default to the method that is directly implemented on the type
-    person.fly();
+    person.fly(&mut stdout);
 
     // To call the fly() methods on the `Pilot` or `Wizard` trait, we need to use more explict syntax
     let person = Human;
     // Specifying the trait name before the method name clarifies to Rust which implementation of fly() we want to call
-    Pilot::fly(&person);
-    Wizard::fly(&person);
-    person.fly();
+    Pilot::fly(&person, &mut stdout);
+    Wizard::fly(&person, &mut stdout);
+    person.fly(&mut stdout);
     // We could also write this to call the method directly implemented on `Human`, but it is more verbose
-    Human::fly(&person);
+    Human::fly(&person, &mut stdout);
 }
 */
 
+// Not in book - helpers that pick an implementation of fly() using fully qualified syntax
+// `<Human as Pilot>::fly` names both the type and the trait, so there's no ambiguity about which fly() is called
+fn fly_as_pilot(h: &Human, out: &mut impl Write) {
+    <Human as Pilot>::fly(h, out);
+}
+
+fn fly_as_wizard(h: &Human, out: &mut impl Write) {
+    <Human as Wizard>::fly(h, out);
+}
+
+// Method call syntax picks the method implemented directly on `Human`
+fn fly_default(h: &Human, out: &mut impl Write) {
+    h.fly(out);
+}
+
 // Because methods takes a `self` parameter, if we had two types that both implement one trait,
 // Rust could figure out which implementation of a trait to use based on the type of `self`
 
@@ -218,6 +239,20 @@ impl Animal for Dog {
     }
 }
 
+// Not in book - a second type implementing `Animal`
+// Now `Animal::baby_name()` could mean either the `Dog` or the `Cat` implementation, so fully qualified syntax is the only way to call it
+struct Cat;
+
+impl Animal for Cat {
+    fn baby_name() -> String {
+        String::from("kitten")
+    }
+}
+
+fn baby_names() -> Vec<String> {
+    vec![<Dog as Animal>::baby_name(), <Cat as Animal>::baby_name()]
+}
+
 fn main() {
     // Will default the baby_name() function that is directly implemented on `Dog`
     println!("A baby dog is called a {}", Dog::baby_name());
@@ -229,6 +264,28 @@ fn main() {
     // To tell Rust which method to use, we need to use fully qualified syntax
     // We provide Rust with a type annotation within angle brackets
     println!("A baby dog is called a {}", <Dog as Animal>::baby_name());
+
+    // Not in book
+    for name in baby_names() {
+        println!("One of the baby animals is called a {}", name);
+    }
+
+    // Not in book - the fly() helpers from the section above, writing to stdout
+    let mut stdout = std::io::stdout();
+    fly_as_pilot(&Human, &mut stdout);
+    fly_as_wizard(&Human, &mut stdout);
+    fly_default(&Human, &mut stdout);
+
+    // Not in book - see outline()
+    println!("{}", outline(&Point { x: 1, y: 3 }));
+    println!("{}", outline("café"));
+
+    // Not in book - see the `Wrapper` newtype below
+    let w = Wrapper::from_parts([String::from("hello"), String::from("world")]);
+    println!("w = {w}");
+    // into_inner() moves the Vec<String> back out, so `w` can't be used after this
+    let items = w.into_inner();
+    println!("w held {} items", items.len());
 }
 
 // In general, fully qualified syntax is defined as follows:
@@ -276,6 +333,24 @@ impl fmt::Display for Point {
 
 impl OutlinePrint for Point {}
 
+// Not in book - a generic function version of outline_print()
+// Any type that implements `Display` can be outlined without needing an `impl OutlinePrint` block
+// Returning a `String` instead of printing lets the caller decide where the output goes
+// The lines are joined without a trailing newline, so `println!("{}", outline(&value))` prints the same box as outline_print()
+// The box is sized by the number of characters rather than bytes, so a value like "café" ('é' is 2 bytes) isn't given an extra column
+fn outline<T: fmt::Display + ?Sized>(value: &T) -> String {
+    let output = value.to_string();
+    let len = output.chars().count();
+    [
+        "*".repeat(len + 4),
+        format!("*{}*", " ".repeat(len + 2)),
+        format!("* {} *", output),
+        format!("*{}*", " ".repeat(len + 2)),
+        "*".repeat(len + 4),
+    ]
+    .join("\n")
+}
+
 //
 // Using the Newtype Pattern to Implement External Traits on External Types
 //
@@ -288,7 +363,7 @@ impl OutlinePrint for Point {}
 // Ex: implement `Display` on `Vec<T>`
 
 // Use a `Wrapper` struct that holds an instance of `Vec<T>`
-struct Wrapper(<Vec<String>);
+struct Wrapper(Vec<String>);
 
 // Implement `Display` on `Wrapper`
 impl fmt::Display for Wrapper {
@@ -300,4 +375,108 @@ impl fmt::Display for Wrapper {
 
 // The downside of using the Newtype pattern is that `Wrapper` is a new type, so it doesn't have the methods of the value it is holding
 // If we wanted the new type to have every method the inner type has, implement the `Deref` trait on the `Wrapper` to return the inner type
-// If we don't want the new type to have all the methods of the inner type, implement the methods we want manually
\ No newline at end of file
+// If we don't want the new type to have all the methods of the inner type, implement the methods we want manually
+
+// Not in book - converting to and from the inner type explicitly
+// into_inner() takes ownership of the `Wrapper` and moves the inner `Vec<String>` out, so nothing is cloned
+// from_parts() accepts anything that can be iterated over as `String`s, not just a `Vec<String>`
+impl Wrapper {
+    fn into_inner(self) -> Vec<String> {
+        self.0
+    }
+
+    fn from_parts(items: impl IntoIterator<Item = String>) -> Wrapper {
+        Wrapper(items.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outline_short_value() {
+        assert_eq!(outline(&1), "*****\n*   *\n* 1 *\n*   *\n*****");
+    }
+
+    #[test]
+    fn outline_long_value() {
+        let expected = "\
+**********************
+*                    *
+* (1000000, -250000) *
+*                    *
+**********************";
+        let p = Point {
+            x: 1000000,
+            y: -250000,
+        };
+        assert_eq!(outline(&p), expected);
+    }
+
+    #[test]
+    fn outline_works_on_str() {
+        let expected = "\
+*********
+*       *
+* hello *
+*       *
+*********";
+        assert_eq!(outline("hello"), expected);
+    }
+
+    #[test]
+    fn outline_multi_byte_str() {
+        let expected = "\
+********
+*      *
+* café *
+*      *
+********";
+        assert_eq!(outline("café"), expected);
+    }
+
+    // Vec<u8> implements `Write`, so it can be used as a buffer to capture what fly() writes
+    fn captured(fly: fn(&Human, &mut Vec<u8>)) -> String {
+        let mut buf = Vec::new();
+        fly(&Human, &mut buf);
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn fly_as_pilot_uses_pilot_impl() {
+        assert_eq!(captured(fly_as_pilot), "This is your captain speaking.\n");
+    }
+
+    #[test]
+    fn fly_as_wizard_uses_wizard_impl() {
+        assert_eq!(captured(fly_as_wizard), "Up!\n");
+    }
+
+    #[test]
+    fn fly_default_uses_inherent_method() {
+        assert_eq!(captured(fly_default), "*waving arms furiously*\n");
+    }
+
+    #[test]
+    fn baby_names_uses_animal_impls() {
+        // "Spot" would show up here if the inherent Dog::baby_name() was called instead
+        assert_eq!(baby_names(), vec!["puppy", "kitten"]);
+    }
+
+    #[test]
+    fn wrapper_round_trips_a_vec() {
+        let items = vec![String::from("hello"), String::from("world")];
+        let w = Wrapper::from_parts(items.clone());
+
+        assert_eq!(w.to_string(), "[hello, world]");
+        assert_eq!(w.into_inner(), items);
+    }
+
+    #[test]
+    fn wrapper_from_str_iterator() {
+        let w = Wrapper::from_parts(["a", "b", "c"].iter().map(|s| s.to_string()));
+
+        assert_eq!(w.into_inner(), vec!["a", "b", "c"]);
+    }
+}
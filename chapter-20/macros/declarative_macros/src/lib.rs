@@ -81,3 +81,301 @@ macro_rules! vec_def {
     }
      */
 }
+
+//
+// Matching Literal Tokens in a Pattern
+//
+
+// Note: not in book
+// A `hashmap!` macro, similar to `vec!` but for key-value pairs: `hashmap! { "a" => 1, "b" => 2 }`
+// Any tokens in a pattern that aren't macro variables (like `=>` and `with_capacity:`) have to appear literally in the macro call
+// The types of the keys and values are inferred from the pairs, just like a HashMap built by hand
+
+// The full path `::std::collections::HashMap` is used, so the macro works even if the caller hasn't imported HashMap
+#[macro_export]
+macro_rules! hashmap {
+    // `hashmap! { with_capacity: 10; "a" => 1 }` reserves space for the pairs up front, so the map doesn't need to grow while they are inserted
+    // This arm comes first, since macro arms are tried from top to bottom
+    ( with_capacity: $cap:expr; $( $k:expr => $v:expr ),* $(,)? ) => {
+        {
+            #[allow(unused_mut)]
+            let mut map = ::std::collections::HashMap::with_capacity($cap);
+            $(
+                map.insert($k, $v);
+            )*
+            map
+        }
+    };
+    // `$(,)?` allows an optional trailing comma after the last pair
+    ( $( $k:expr => $v:expr ),* $(,)? ) => {
+        {
+            // `map` is never changed when the macro is called with no pairs
+            #[allow(unused_mut)]
+            let mut map = ::std::collections::HashMap::new();
+            $(
+                map.insert($k, $v);
+            )*
+            map
+        }
+    };
+}
+
+//
+// Repeating Inside a Group
+//
+
+// Note: not in book
+// `tuple!(a, b, c)` expands into `(a, b, c)`, and each element can be a different type
+// A function can't do this, since it would need a different signature for every number of elements (and every combination of types)
+// The `,` after `$x` is inside the repetition, so a 1-element call expands to `(a,)` which is a tuple, instead of `(a)` which is just `a` in parentheses
+// The macro accepts any number of elements, but traits like Debug and PartialEq are only implemented for tuples of up to 12 elements
+#[macro_export]
+macro_rules! tuple {
+    ( $( $x:expr ),* $(,)? ) => {
+        ( $( $x, )* )
+    };
+}
+
+//
+// Short-Circuiting Inside a Macro
+//
+
+// Note: not in book
+// `try_vec![a, b, c]` takes expressions that each return a Result, and collects the Ok values into `Ok(vec![...])`
+// If any expression returns an Err, the expressions after it aren't evaluated, and the whole macro returns that Err
+
+// This can't use the `?` operator, since `?` would return from the function the macro is called in, instead of giving back a Result
+// Instead, the expansion is a labeled block, and `break 'items Err(e)` leaves the block early with the error as its value
+// Like `hashmap!`, the expansion uses full paths (including `::std::vec!`), so it still works in a crate that defines its own `vec!`
+#[macro_export]
+macro_rules! try_vec {
+    ( $( $x:expr ),* $(,)? ) => {
+        {
+            // The label isn't used when the macro is called with no expressions
+            #[allow(unused_labels)]
+            let items = 'items: {
+                ::std::result::Result::Ok(::std::vec![
+                    $(
+                        match $x {
+                            ::std::result::Result::Ok(value) => value,
+                            ::std::result::Result::Err(e) => break 'items ::std::result::Result::Err(e),
+                        }
+                    ),*
+                ])
+            };
+            items
+        }
+    };
+}
+
+//
+// Optional Macro Arguments
+//
+
+// Note: not in book
+// Floats can't be reliably compared with `assert_eq!`, since rounding errors mean 0.1 + 0.2 isn't exactly 0.3
+// `assert_approx!(a, b, eps)` passes if `a` and `b` are at most `eps` apart
+// Macros can't have default arguments like functions in some languages, but a second arm can fill in the missing argument:
+// `assert_approx!(a, b)` calls the macro again with an epsilon of 1e-9, which matches the second arm
+#[macro_export]
+macro_rules! assert_approx {
+    ( $left:expr, $right:expr $(,)? ) => {
+        $crate::assert_approx!($left, $right, 1e-9)
+    };
+    ( $left:expr, $right:expr, $eps:expr $(,)? ) => {
+        {
+            // Like `assert_eq!`, each expression is only evaluated once, even though it is used in the check and in the panic message
+            // The type annotation lets float literals like `1.0` be used, which would otherwise have an ambiguous float type
+            let (left, right, eps): (f64, f64, f64) = ($left, $right, $eps);
+            let difference = (left - right).abs();
+            if difference > eps {
+                panic!(
+                    "assertion failed: `(left ≈ right)`\n  left: `{:?}`\n right: `{:?}`\n  diff: `{:?}` is greater than `{:?}`",
+                    left, right, difference, eps
+                );
+            }
+        }
+    };
+}
+
+//
+// Turning Code into Strings with `stringify!`
+//
+
+// Note: not in book
+// `debug_all!(x, y.len(), z)` prints each expression's Debug format, labeled with the source code of the expression:
+/*
+x = 5
+y.len() = 3
+z = "hello"
+ */
+// `stringify!` turns the tokens of `$x` into a string literal at compile time (the same way the HelloMacro derive gets the name of the type)
+// Like `dbg!`, the output goes to stderr, so it doesn't mix in with the program's normal output
+
+// `debug_all!(to out; x, y)` writes the lines to `out` (anything that implements std::io::Write) instead
+#[macro_export]
+macro_rules! debug_all {
+    ( to $out:expr; $( $x:expr ),+ $(,)? ) => {
+        {
+            // writeln!() calls the write_fmt() method, so the Write trait needs to be in scope where the macro is expanded
+            use ::std::io::Write as _;
+            let out = &mut $out;
+            $(
+                writeln!(out, "{} = {:?}", stringify!($x), $x).unwrap();
+            )+
+        }
+    };
+    ( $( $x:expr ),+ $(,)? ) => {
+        $crate::debug_all!(to ::std::io::stderr(); $( $x ),+)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    #[test]
+    fn hashmap_from_pairs() {
+        let map = hashmap! {
+            "one" => 1,
+            "two" => 2,
+        };
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["one"], 1);
+        assert_eq!(map["two"], 2);
+    }
+
+    #[test]
+    fn empty_hashmap() {
+        let map: HashMap<String, i32> = hashmap! {};
+
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn hashmap_with_capacity() {
+        let map = hashmap! { with_capacity: 16; 'a' => "apple", 'b' => "banana" };
+
+        assert!(map.capacity() >= 16);
+        assert_eq!(map.get(&'a'), Some(&"apple"));
+        assert_eq!(map.get(&'b'), Some(&"banana"));
+        assert_eq!(map.get(&'c'), None);
+    }
+
+    #[test]
+    fn empty_tuple() {
+        let unit: () = tuple!();
+
+        assert_eq!(unit, ());
+    }
+
+    #[test]
+    fn one_element_tuple() {
+        let single = tuple!(5);
+
+        // A 1-element tuple, not a plain i32
+        assert_eq!(single, (5,));
+        assert_eq!(single.0, 5);
+    }
+
+    #[test]
+    fn mixed_type_tuple() {
+        let mixed = tuple!(1, "two", 3.0);
+
+        assert_eq!(mixed, (1, "two", 3.0));
+    }
+
+    #[test]
+    fn try_vec_all_ok() {
+        let items: Result<Vec<i32>, String> =
+            try_vec![Ok(1), "2".parse::<i32>().map_err(|e| e.to_string()), Ok(3)];
+
+        assert_eq!(items, Ok(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn try_vec_stops_at_first_err() {
+        let mut evaluated = Vec::new();
+        let mut step = |n: i32, result: Result<i32, &'static str>| {
+            evaluated.push(n);
+            result
+        };
+
+        let items = try_vec![
+            step(1, Ok(1)),
+            step(2, Err("second failed")),
+            step(3, Err("third failed"))
+        ];
+
+        assert_eq!(items, Err("second failed"));
+        // The third expression is never evaluated
+        assert_eq!(evaluated, vec![1, 2]);
+    }
+
+    #[test]
+    fn try_vec_in_function_returning_result() {
+        fn parse_all(a: &str, b: &str) -> Result<Vec<u8>, std::num::ParseIntError> {
+            let numbers = try_vec![a.parse(), b.parse()]?;
+            Ok(numbers)
+        }
+
+        assert_eq!(parse_all("4", "2"), Ok(vec![4, 2]));
+        assert!(parse_all("4", "two").is_err());
+    }
+
+    #[test]
+    fn try_vec_with_local_vec_macro() {
+        // A `vec!` defined here would be used by an expansion that calls `vec!` instead of `::std::vec!`
+        // try_vec! doesn't use it, so it's never used
+        #[allow(unused_macros)]
+        macro_rules! vec {
+            ( $( $x:expr ),* ) => {
+                "not a Vec"
+            };
+        }
+
+        let items: Result<Vec<i32>, String> = try_vec![Ok(1), Ok(2)];
+        assert_eq!(items, Ok(std::vec![1, 2]));
+    }
+
+    #[test]
+    fn assert_approx_passes_within_epsilon() {
+        assert_approx!(0.1 + 0.2, 0.3);
+        assert_approx!(1.0 / 3.0, 0.33333, 1e-5);
+    }
+
+    #[test]
+    #[should_panic(expected = "is greater than `0.001`")]
+    fn assert_approx_fails_outside_epsilon() {
+        assert_approx!(1.0, 1.01, 0.001);
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion failed: `(left ≈ right)`")]
+    fn assert_approx_default_epsilon_fails() {
+        // 1e-6 apart is close, but not within the default epsilon of 1e-9
+        assert_approx!(1.0, 1.000001);
+    }
+
+    #[test]
+    fn debug_all_labels_each_expression() {
+        let x = 5;
+        let name = "hello";
+        let v = [1, 2, 3];
+
+        let mut buffer = Vec::new();
+        debug_all!(to buffer; x, name, v.len(), x + 1);
+
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "x = 5\nname = \"hello\"\nv.len() = 3\nx + 1 = 6\n"
+        );
+    }
+
+    #[test]
+    fn debug_all_to_stderr_compiles() {
+        let x = Some(1);
+        debug_all!(x);
+    }
+}
@@ -49,3 +49,143 @@ fn impl_hello_macro(ast: &syn::DeriveInput) -> TokenStream {
     };
     gen.into()
 }
+
+// Note: not in book
+// A second derive macro in the same crate, for the `Named` trait
+// It follows the same split as `hello_macro_derive`, the outer function parses the `TokenStream` and the inner function builds the impl
+#[proc_macro_derive(Named)]
+pub fn named_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+
+    impl_named(&ast)
+}
+
+fn impl_named(ast: &syn::DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+    let gen = quote! {
+        impl Named for #name {
+            fn type_name() -> &'static str {
+                stringify!(#name)
+            }
+        }
+    };
+    gen.into()
+}
+
+// Note: not in book
+// `#[derive(Builder)]` on a struct `Foo` generates a `FooBuilder` struct, with one setter method per field and a build() method:
+/*
+#[derive(Builder)]
+struct Order {
+    item: String,
+    note: Option<String>,
+}
+
+let order = Order::builder().item(String::from("pancakes")).build()?;
+ */
+// Every field is stored as an Option in the builder, so build() can tell which fields were never set
+// build() returns an Err naming the first required field that is missing, fields that are already an Option are left as None
+// Unlike `HelloMacro` and `Named`, there is no trait to implement, the macro only adds new items next to the struct
+#[proc_macro_derive(Builder)]
+pub fn builder_derive(input: TokenStream) -> TokenStream {
+    let ast = syn::parse(input).unwrap();
+
+    impl_builder(&ast)
+}
+
+// Returns `T` if `ty` is `Option<T>`
+// The macro only sees tokens, so this checks the name of the type, a type alias for Option wouldn't be recognised
+fn option_inner(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        syn::GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    }
+}
+
+fn impl_builder(ast: &syn::DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+    let vis = &ast.vis;
+    // `format_ident!` builds a new identifier, like `format!` builds a String
+    let builder_name = quote::format_ident!("{}Builder", name);
+
+    // A builder only makes sense for a struct with named fields, so anything else is a compile-time error for the user
+    let fields = match &ast.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => panic!("Builder can only be derived for structs with named fields"),
+    };
+
+    let mut builder_fields = Vec::new();
+    let mut setters = Vec::new();
+    let mut build_fields = Vec::new();
+    for field in fields {
+        // Named fields always have an ident
+        let field_name = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+
+        match option_inner(ty) {
+            // Optional fields are stored as they are, and the setter takes the inner type
+            Some(inner) => {
+                builder_fields.push(quote! { #field_name: #ty });
+                setters.push(quote! {
+                    #vis fn #field_name(mut self, value: #inner) -> #builder_name {
+                        self.#field_name = Some(value);
+                        self
+                    }
+                });
+                build_fields.push(quote! { #field_name: self.#field_name });
+            }
+            // Required fields are wrapped in an Option, which build() checks
+            None => {
+                builder_fields.push(quote! { #field_name: Option<#ty> });
+                setters.push(quote! {
+                    #vis fn #field_name(mut self, value: #ty) -> #builder_name {
+                        self.#field_name = Some(value);
+                        self
+                    }
+                });
+                let missing = format!("missing field `{}`", field_name);
+                build_fields.push(quote! {
+                    #field_name: self.#field_name.ok_or_else(|| String::from(#missing))?
+                });
+            }
+        }
+    }
+
+    let gen = quote! {
+        // Every field is an Option, so the builder can derive Default to start with every field unset
+        #[derive(Default)]
+        #vis struct #builder_name {
+            #(#builder_fields,)*
+        }
+
+        impl #builder_name {
+            #(#setters)*
+
+            #vis fn build(self) -> Result<#name, String> {
+                Ok(#name {
+                    #(#build_fields,)*
+                })
+            }
+        }
+
+        impl #name {
+            #vis fn builder() -> #builder_name {
+                #builder_name::default()
+            }
+        }
+    };
+    gen.into()
+}
@@ -8,3 +8,10 @@ pub trait HelloMacro {
     // Rust does not have reflection capabilities, so it can't look up the type's name at runtime
     fn hello_macro();
 }
+
+// Note: not in book
+// A second trait with its own derive macro (`#[derive(Named)]` in `hello_macro_derive`)
+// Instead of printing the name, type_name() returns it, so it can be used by the caller
+pub trait Named {
+    fn type_name() -> &'static str;
+}
@@ -1,9 +1,68 @@
-use hello_macro::HelloMacro;
-use hello_macro_derive::HelloMacro;
+use hello_macro::{HelloMacro, Named};
+use hello_macro_derive::{Builder, HelloMacro, Named};
 
-#[derive(HelloMacro)]
+#[derive(HelloMacro, Named)]
 struct Pancakes;
 
 fn main() {
     Pancakes::hello_macro();
+    println!("type_name() returns {}", Pancakes::type_name());
+
+    let order = Order::builder()
+        .item(String::from("Pancakes"))
+        .syrup(String::from("Maple"))
+        .build();
+    println!("{order:?}");
+}
+
+#[derive(Builder, Debug, PartialEq)]
+struct Order {
+    item: String,
+    syrup: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Named)]
+    struct Waffles {
+        _toppings: Vec<String>,
+    }
+
+    #[test]
+    fn each_type_gets_its_own_name() {
+        assert_eq!(Pancakes::type_name(), "Pancakes");
+        assert_eq!(Waffles::type_name(), "Waffles");
+    }
+
+    #[test]
+    fn builder_sets_fields() {
+        let order = Order::builder()
+            .item(String::from("Waffles"))
+            .syrup(String::from("Maple"))
+            .build();
+
+        assert_eq!(
+            order,
+            Ok(Order {
+                item: String::from("Waffles"),
+                syrup: Some(String::from("Maple")),
+            })
+        );
+    }
+
+    #[test]
+    fn builder_leaves_optional_fields_unset() {
+        let order = Order::builder().item(String::from("Waffles")).build();
+
+        assert_eq!(order.unwrap().syrup, None);
+    }
+
+    #[test]
+    fn builder_errors_on_missing_required_field() {
+        let order = Order::builder().syrup(String::from("Maple")).build();
+
+        assert_eq!(order, Err(String::from("missing field `item`")));
+    }
 }
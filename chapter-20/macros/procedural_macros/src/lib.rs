@@ -8,6 +8,7 @@
 
 // When creating procedural macros, the definitions must reside in their own crate with a special crate type.
 
+/*
 use proc_macro;
 
 // `some_attribute` is a placeholder for using a specific macro variety
@@ -19,3 +20,122 @@ use proc_macro;
 // The function also has an attribute attached to it that specifies which kind of procedural macro we're creating
 // We can have multiple kinds of procedural macros in the same crate
 pub fn some_name(input: TokenStream) -> TokenStream {}
+*/
+
+// Note: not in book - the placeholder above is commented out, since `some_attribute` doesn't exist
+// The macros below are real procedural macros, so Cargo.toml marks this crate with `proc-macro = true` (like `hello_macro_derive`)
+// syn needs the "full" feature to parse items like functions (by default it can only parse what a derive macro needs)
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Expr, Ident, ItemFn, Path, Token};
+
+//
+// An Attribute-like Macro
+//
+
+// Note: not in book
+// `#[log_entry]` rewrites the function it is attached to, so that it prints "entering {function name}" before running the original body
+// `#[log_entry(some_fn)]` passes the message to `some_fn` (which takes a &str) instead of printing it, e.g. to send it to a logger
+// See Attribute-like-macros.md for the two `TokenStream` parameters
+#[proc_macro_attribute]
+pub fn log_entry(attr: TokenStream, item: TokenStream) -> TokenStream {
+    // The attribute's contents are empty for a plain `#[log_entry]`
+    let logger: Option<Path> = if attr.is_empty() {
+        None
+    } else {
+        Some(syn::parse(attr).unwrap())
+    };
+    // `ItemFn` is the syntax tree of a function: its attributes, visibility, signature and body
+    let func = syn::parse(item).unwrap();
+
+    impl_log_entry(&func, logger)
+}
+
+fn impl_log_entry(func: &ItemFn, logger: Option<Path>) -> TokenStream {
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = func;
+    let name = &sig.ident;
+
+    // `concat!` joins the string literals at compile time, so no String is allocated when the function is called
+    let message = quote! { concat!("entering ", stringify!(#name)) };
+    let prologue = match logger {
+        Some(logger) => quote! { #logger(#message); },
+        None => quote! { println!("{}", #message); },
+    };
+
+    // `#(#attrs)*` repeats for each attribute, like `$()*` in a declarative macro
+    // The original body is kept as its own block, so its variables can't clash with anything in the prologue
+    let gen = quote! {
+        #(#attrs)*
+        #vis #sig {
+            #prologue
+            #block
+        }
+    };
+    gen.into()
+}
+
+//
+// A Function-like Macro
+//
+
+// Note: not in book
+// `make_table!(a => 1, b => 2)` expands into a lookup function:
+/*
+fn lookup(key: &str) -> Option<i32> {
+    match key {
+        "a" => Some(1),
+        "b" => Some(2),
+        _ => None,
+    }
+}
+ */
+// See Function-like-macros.md - the `TokenStream` passed in is everything inside the parentheses
+
+// syn doesn't know what `a => 1` means, so we describe it with a struct and implement syn's `Parse` trait for it
+struct Entry {
+    key: Ident,
+    value: Expr,
+}
+
+impl Parse for Entry {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key = input.parse()?;
+        // `Token![=>]` is the type syn uses for the `=>` token, it is parsed to check that it is there, and then thrown away
+        input.parse::<Token![=>]>()?;
+        let value = input.parse()?;
+        Ok(Entry { key, value })
+    }
+}
+
+#[proc_macro]
+pub fn make_table(input: TokenStream) -> TokenStream {
+    // `Punctuated` parses a list of entries seperated by commas (allowing a trailing comma)
+    let parser = Punctuated::<Entry, Token![,]>::parse_terminated;
+    let entries = syn::parse::Parser::parse(parser, input).unwrap();
+
+    impl_make_table(&entries)
+}
+
+fn impl_make_table(entries: &Punctuated<Entry, Token![,]>) -> TokenStream {
+    // Build one match arm per entry, the keys are turned into string literals with `stringify!`
+    let arms = entries.iter().map(|Entry { key, value }| {
+        quote! { stringify!(#key) => Some(#value), }
+    });
+
+    let gen = quote! {
+        fn lookup(key: &str) -> Option<i32> {
+            match key {
+                #(#arms)*
+                _ => None,
+            }
+        }
+    };
+    gen.into()
+}
@@ -1,3 +1,17 @@
+// Note: not in book - using the macros defined in lib.rs
+use functional_macros::{log_entry, make_table};
+
+// Generates `fn lookup(key: &str) -> Option<i32>`
+make_table!(one => 1, two => 2);
+
+#[log_entry]
+fn greet(name: &str) {
+    println!("Hello, {name}!");
+}
+
 fn main() {
-    println!("Hello, world!");
+    // Prints "entering greet" and then "Hello, world!"
+    greet("world");
+
+    println!("{:?} {:?}", lookup("two"), lookup("three"));
 }
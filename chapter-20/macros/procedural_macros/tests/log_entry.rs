@@ -0,0 +1,40 @@
+use functional_macros::log_entry;
+use std::cell::RefCell;
+
+thread_local! {
+    // Each test runs on its own thread, so tests don't see each other's messages
+    static LOG: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+fn record(message: &str) {
+    LOG.with(|log| log.borrow_mut().push(String::from(message)));
+}
+
+fn logged() -> Vec<String> {
+    LOG.with(|log| log.borrow().clone())
+}
+
+#[log_entry(record)]
+fn add(a: i32, b: i32) -> i32 {
+    record("adding");
+    a + b
+}
+
+#[log_entry]
+fn printed() -> &'static str {
+    "body still runs"
+}
+
+#[test]
+fn logs_entry_before_body() {
+    assert_eq!(add(2, 3), 5);
+    assert_eq!(logged(), vec!["entering add", "adding"]);
+
+    add(1, 1);
+    assert_eq!(logged().len(), 4);
+}
+
+#[test]
+fn default_prints_and_keeps_return_value() {
+    assert_eq!(printed(), "body still runs");
+}
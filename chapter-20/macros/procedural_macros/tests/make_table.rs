@@ -0,0 +1,17 @@
+use functional_macros::make_table;
+
+make_table!(a => 1, b => 2, negative => -3,);
+
+#[test]
+fn lookup_hits() {
+    assert_eq!(lookup("a"), Some(1));
+    assert_eq!(lookup("b"), Some(2));
+    assert_eq!(lookup("negative"), Some(-3));
+}
+
+#[test]
+fn lookup_miss() {
+    assert_eq!(lookup("c"), None);
+    // The keys are matched exactly
+    assert_eq!(lookup("A"), None);
+}
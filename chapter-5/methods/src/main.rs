@@ -1,6 +1,11 @@
 // Opt in to printing out debug info for the struct
-#[derive(Debug)]
+// Note: not in book - PartialEq lets tests compare rectangles with assert_eq!
+#[derive(Debug, PartialEq)]
 struct Rectangle {
+    // Note: not in book - the position of the top-left corner, with `y` growing downwards like screen coordinates (see union())
+    // The position can be negative (e.g. partly off the left of the screen), so x and y are i32s
+    x: i32,
+    y: i32,
     width: u32,
     height: u32,
 }
@@ -8,6 +13,9 @@ struct Rectangle {
 // impl (implementation) blocks will contain associated functions attached to the particular struct type
 impl Rectangle {
     // The first parameter of a method is always &self, which is usually a reference to the caller of the struct (can be made mutable with &mut self)
+    // Note: not in book - the area of a large rectangle might not fit in a u32 (e.g. 100_000 by 100_000)
+    // When that happens, this panics in a debug build, and wraps around to a wrong area in a release build
+    // See area_checked() for a version that can't overflow
     fn area(&self) -> u32 {
         self.width * self.height
     }
@@ -26,6 +34,8 @@ impl Rectangle {
     // The Self keyword is an alias for the type that appears after impl
     fn square(size: u32) -> Self {
         Self {
+            x: 0,
+            y: 0,
             width: size,
             height: size,
         }
@@ -38,6 +48,8 @@ impl Rectangle {
     // NOTE, because self is not a reference here, this method will take ownership of the caller!
     fn max(self, other: Rectangle) -> Rectangle {
         Rectangle {
+            x: self.x,
+            y: self.y,
             width: self.width.max(other.width),
             height: self.height.max(other.height)
         }
@@ -57,8 +69,54 @@ impl Rectangle {
 //     }
 // }
 
+// Note: not in book - more methods for layout math, in their own impl block
+impl Rectangle {
+    // The width divided by the height, e.g. 2.0 for a rectangle twice as wide as it is tall
+    // The fields are converted to f64 first, since dividing two u32s would throw away the fractional part (16 / 9 is 1)
+    // A rectangle with a height of 0 returns f64::INFINITY, which is what dividing a positive number by 0.0 gives
+    // This includes a 0 by 0 rectangle, which would otherwise be 0.0 / 0.0 (f64::NAN, which isn't equal to anything, even itself)
+    fn aspect_ratio(&self) -> f64 {
+        if self.height == 0 {
+            return f64::INFINITY;
+        }
+        self.width as f64 / self.height as f64
+    }
+
+    fn is_square(&self) -> bool {
+        self.width == self.height
+    }
+
+    // Like area(), but the area is worked out as a u64
+    // The largest possible area (u32::MAX * u32::MAX) is smaller than u64::MAX, so converting before multiplying means it won't overflow
+    // checked_mul() returns None instead of overflowing, which would only matter if the fields were ever made wider than u32
+    fn area_checked(&self) -> Option<u64> {
+        (self.width as u64).checked_mul(self.height as u64)
+    }
+
+    // The smallest rectangle that contains both `self` and `other` (their bounding box)
+    // The rectangles don't need to overlap, any gap between them is included in the result
+    // The edges are worked out as i64s, since x + width could be larger than i32::MAX
+    // The distance between the edges can still be larger than u32::MAX (e.g. from x = i32::MIN to past x = i32::MAX),
+    // so the width and height saturate at u32::MAX instead of wrapping around to a smaller number
+    fn union(&self, other: &Rectangle) -> Rectangle {
+        let left = self.x.min(other.x);
+        let top = self.y.min(other.y);
+        let right = (self.x as i64 + self.width as i64).max(other.x as i64 + other.width as i64);
+        let bottom = (self.y as i64 + self.height as i64).max(other.y as i64 + other.height as i64);
+
+        Rectangle {
+            x: left,
+            y: top,
+            width: u32::try_from(right - left as i64).unwrap_or(u32::MAX),
+            height: u32::try_from(bottom - top as i64).unwrap_or(u32::MAX),
+        }
+    }
+}
+
 fn main() {
     let rect1 = Rectangle {
+        x: 0,
+        y: 0,
         width: 30,
         height: 50,
     };
@@ -75,10 +133,14 @@ fn main() {
     }
 
     let rect2 = Rectangle {
+        x: 0,
+        y: 0,
         width: 10,
         height: 40,
     };
     let rect3 = Rectangle {
+        x: 0,
+        y: 0,
         width: 60,
         height: 45,
     };
@@ -89,8 +151,32 @@ fn main() {
     // To call associated functions, use the :: syntax with the struct name
     let sq = Rectangle::square(3);
 
+    // Note: not in book
+    println!("rect1 has an aspect ratio of {}", rect1.aspect_ratio());
+    println!("Is sq a square? {}", sq.is_square());
+    let huge = Rectangle::square(100_000);
+    println!(
+        "A 100000 by 100000 rectangle has an area of {:?}",
+        huge.area_checked()
+    );
+    let button = Rectangle {
+        x: 10,
+        y: 10,
+        width: 50,
+        height: 20,
+    };
+    let label = Rectangle {
+        x: 70,
+        y: 15,
+        width: 30,
+        height: 10,
+    };
+    println!("Both fit in {:?}", button.union(&label));
+
     // Method calls are syntatic sugar for associated function calls
     let mut r = Rectangle {
+        x: 0,
+        y: 0,
         width: 1,
         height: 2
     };
@@ -105,6 +191,8 @@ fn main() {
 
     // Rust will take care of references/dereferences when using dot notation for methods
     let r = &mut Box::new(Rectangle {
+        x: 0,
+        y: 0,
         width: 1,
         height: 2
     });
@@ -119,6 +207,8 @@ fn main() {
 
     // rect will have read and owned permissions
     let rect = Rectangle {
+        x: 0,
+        y: 0,
         width: 0,
         height: 0,
     };
@@ -126,7 +216,7 @@ fn main() {
     // We can call area and max on rect
     println!("{}", rect.area());
 
-    let other_rect = Rectangle { width: 1, height: 1};
+    let other_rect = Rectangle { x: 0, y: 0, width: 1, height: 1};
 
     let max_rect = rect.max(other_rect);
 
@@ -139,6 +229,8 @@ fn main() {
 
     // A similar error will occur for non-mutable references to mutable objects
     let mut rect = Rectangle {
+        x: 0,
+        y: 0,
         width: 0,
         height: 0
     };
@@ -152,11 +244,15 @@ fn main() {
 
     // Calling methods with a 'self' parameter (as opposed to &self) will move the input struct
     let rect = Rectangle {
+        x: 0,
+        y: 0,
         width: 0,
         height: 0,
     };
 
     let other_rect = Rectangle {
+        x: 0,
+        y: 0,
         width: 1,
         height: 1
     };
@@ -165,4 +261,122 @@ fn main() {
 
     // Won't work, as rect lost read and owner permissions when max_rect was called (the underlying object on the heap was moved into the scope of max_rect)
     //println!("{}", rect.area());    
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aspect_ratio_of_rectangle() {
+        let rect = Rectangle {
+            x: 0,
+            y: 0,
+            width: 16,
+            height: 9,
+        };
+
+        assert_eq!(rect.aspect_ratio(), 16.0 / 9.0);
+        assert!(!rect.is_square());
+    }
+
+    #[test]
+    fn aspect_ratio_of_square() {
+        let sq = Rectangle::square(5);
+
+        assert_eq!(sq.aspect_ratio(), 1.0);
+        assert!(sq.is_square());
+    }
+
+    #[test]
+    fn aspect_ratio_of_zero_height() {
+        let flat = Rectangle {
+            x: 0,
+            y: 0,
+            width: 10,
+            height: 0,
+        };
+        assert_eq!(flat.aspect_ratio(), f64::INFINITY);
+
+        // Not NaN, even though both sides are 0
+        let empty = Rectangle::square(0);
+        assert_eq!(empty.aspect_ratio(), f64::INFINITY);
+        assert!(empty.is_square());
+    }
+
+    #[test]
+    fn area_checked_matches_area() {
+        let rect = Rectangle {
+            x: 0,
+            y: 0,
+            width: 30,
+            height: 50,
+        };
+
+        assert_eq!(rect.area_checked(), Some(1500));
+        assert_eq!(rect.area_checked(), Some(rect.area() as u64));
+    }
+
+    #[test]
+    fn area_checked_wider_than_u32() {
+        // 100_000 * 100_000 = 10_000_000_000, which is larger than u32::MAX (4_294_967_295)
+        let rect = Rectangle::square(100_000);
+        assert_eq!(rect.area_checked(), Some(10_000_000_000));
+
+        let max = Rectangle::square(u32::MAX);
+        assert_eq!(max.area_checked(), Some(u32::MAX as u64 * u32::MAX as u64));
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow")]
+    #[cfg(debug_assertions)]
+    fn area_overflows_in_debug() {
+        Rectangle::square(100_000).area();
+    }
+
+    fn placed(x: i32, y: i32, width: u32, height: u32) -> Rectangle {
+        Rectangle {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn union_of_overlapping_rectangles() {
+        let a = placed(0, 0, 10, 10);
+        let b = placed(5, -5, 10, 10);
+
+        // Spans x from 0 to 15, and y from -5 to 10
+        assert_eq!(a.union(&b), placed(0, -5, 15, 15));
+        // The order doesn't matter
+        assert_eq!(b.union(&a), placed(0, -5, 15, 15));
+    }
+
+    #[test]
+    fn union_of_disjoint_rectangles() {
+        let a = placed(0, 0, 2, 2);
+        let b = placed(10, 20, 5, 5);
+
+        // The gap between them is part of the bounding box
+        assert_eq!(a.union(&b), placed(0, 0, 15, 25));
+    }
+
+    #[test]
+    fn union_with_contained_rectangle() {
+        let outer = placed(-10, -10, 100, 100);
+        let inner = placed(0, 0, 1, 1);
+
+        assert_eq!(outer.union(&inner), placed(-10, -10, 100, 100));
+    }
+
+    #[test]
+    fn union_wider_than_u32_saturates() {
+        let left = placed(i32::MIN, 0, 0, 1);
+        let right = placed(i32::MAX, 0, u32::MAX, 1);
+
+        // The span is nearly 2 * u32::MAX, so casting it with `as u32` would wrap around to u32::MAX - 1
+        assert_eq!(left.union(&right), placed(i32::MIN, 0, u32::MAX, 1));
+    }
+}
@@ -1,5 +1,7 @@
 // Need to import the library first, unlike with vectors and strings
 use std::collections::HashMap;
+// Note: not in book - needed for the `T: Eq + Hash` bound on histogram()
+use std::hash::Hash;
 
 fn main() {
     //
@@ -98,4 +100,127 @@ fn main() {
     }
 
     println!("{:?}", map);
+
+    // Note: not in book - see histogram() after main()
+    let counts = histogram(text.split_whitespace());
+    println!("Most common words: {:?}", top_n(&counts, 2));
+
+    // Note: not in book - see fib_memo() after main()
+    println!("fib(90) = {} = {}", fib_memo(90), fib_iter(90));
+}
+
+//
+// Counting Anything in a Hash Map
+//
+
+// Note: not in book
+// The word counting above works for any type that can be a HashMap key, not just &str
+// HashMap keys need to be compared for equality and hashed, which is what the `Eq + Hash` bounds ask for
+// `impl IntoIterator` accepts a Vec, an array, a range, or an iterator like the one split_whitespace() returns
+fn histogram<T: Eq + Hash>(items: impl IntoIterator<Item = T>) -> HashMap<T, usize> {
+    let mut counts = HashMap::new();
+    for item in items {
+        *counts.entry(item).or_insert(0) += 1;
+    }
+    counts
+}
+
+// Returns the `n` most common items and their counts, most common first
+// A HashMap has no order, so items with the same count are sorted by the item itself to always give the same result (this needs `Ord`)
+// The items are cloned, so the histogram can still be used afterwards
+fn top_n<T: Ord + Clone>(hist: &HashMap<T, usize>, n: usize) -> Vec<(T, usize)> {
+    let mut counts: Vec<(T, usize)> = hist
+        .iter()
+        .map(|(item, count)| (item.clone(), *count))
+        .collect();
+    counts.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+    counts.truncate(n);
+    counts
+}
+
+//
+// Caching Results in a Hash Map
+//
+
+// Note: not in book
+// The recursive definition of the Fibonacci numbers (fib(n) = fib(n - 1) + fib(n - 2)) calls itself twice for each number,
+// so fib(90) would make more calls than could finish in a lifetime, most of them working out the same numbers over and over
+// fib_memo() keeps a HashMap from n to fib(n), so each number is only worked out once (this is called memoization)
+// u128 is used since the numbers grow quickly, fib(186) is the largest one that fits
+fn fib_memo(n: u64) -> u128 {
+    // The cache is created here and passed down by mutable reference, so callers don't need to know about it
+    fn fib(n: u64, cache: &mut HashMap<u64, u128>) -> u128 {
+        if n < 2 {
+            return n as u128;
+        }
+        // The value is copied out of the cache, so the immutable borrow from get() ends before the recursive calls borrow the cache mutably
+        if let Some(&value) = cache.get(&n) {
+            return value;
+        }
+
+        let value = fib(n - 1, cache) + fib(n - 2, cache);
+        cache.insert(n, value);
+        value
+    }
+
+    fib(n, &mut HashMap::new())
+}
+
+// The same numbers without recursion or a cache, for comparison
+// Only the last two numbers are needed to work out the next one, so this doesn't need a HashMap at all
+fn fib_iter(n: u64) -> u128 {
+    let (mut current, mut next) = (0u128, 1u128);
+    for _ in 0..n {
+        (current, next) = (next, current + next);
+    }
+    current
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn histogram_counts_words() {
+        let counts = histogram("the cat and the dog and the bird".split_whitespace());
+
+        assert_eq!(counts.len(), 5);
+        assert_eq!(counts["the"], 3);
+        assert_eq!(counts["and"], 2);
+        assert_eq!(counts["cat"], 1);
+        assert_eq!(counts.get("fish"), None);
+    }
+
+    #[test]
+    fn top_two_words() {
+        let counts = histogram("the cat and the dog and the bird".split_whitespace());
+
+        assert_eq!(top_n(&counts, 2), vec![("the", 3), ("and", 2)]);
+    }
+
+    #[test]
+    fn top_n_breaks_ties_by_item() {
+        let counts = histogram([3, 1, 2, 2, 1, 3]);
+
+        assert_eq!(top_n(&counts, 2), vec![(1, 2), (2, 2)]);
+        // Asking for more items than there are returns all of them
+        assert_eq!(top_n(&counts, 10).len(), 3);
+        assert!(top_n(&histogram(Vec::<char>::new()), 2).is_empty());
+    }
+
+    #[test]
+    fn fib_memo_base_cases() {
+        assert_eq!(fib_memo(0), 0);
+        assert_eq!(fib_memo(1), 1);
+        assert_eq!(fib_memo(2), 1);
+        assert_eq!(fib_memo(10), 55);
+    }
+
+    #[test]
+    fn fib_memo_matches_fib_iter() {
+        for n in 0..=90 {
+            assert_eq!(fib_memo(n), fib_iter(n), "fib({n})");
+        }
+        assert_eq!(fib_iter(90), 2_880_067_194_370_816_120);
+    }
 }
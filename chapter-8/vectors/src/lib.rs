@@ -0,0 +1,427 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+//
+// Reading Elements with an Error Message
+//
+
+// Note: not in book
+// Indexing with [] panics when the index is out of bounds, and get() returns None, which doesn't say what went wrong
+// get_checked() returns a Result instead, with an Err that includes the index and the length, so the caller can report or pass on the problem with `?`
+// It takes a slice, so it works for vectors, arrays and parts of either
+pub fn get_checked<T>(v: &[T], i: usize) -> Result<&T, String> {
+    v.get(i).ok_or_else(|| {
+        format!(
+            "index {i} is out of bounds for a slice of length {}",
+            v.len()
+        )
+    })
+}
+
+//
+// Parsing a Row of Spreadsheet Cells
+//
+
+// Note: not in book
+// The SpreadsheetCell enum from main.rs, made public so parse_row() can return it
+// PartialEq and Debug are derived so rows can be compared with assert_eq!()
+#[derive(Debug, PartialEq)]
+pub enum SpreadsheetCell {
+    Int(i32),
+    Float(f64),
+    Text(String),
+}
+
+// Splits a line of comma-separated values, and works out which variant each cell should be
+// Each field is tried as an i32 first, then as an f64, and anything else is kept as text
+// The order matters, since "3" would also parse as an f64
+// Spaces around a field are trimmed, so "1, 2" is two Ints, and an empty field becomes Text("")
+pub fn parse_row(line: &str) -> Vec<SpreadsheetCell> {
+    line.split(',')
+        .map(|field| {
+            let field = field.trim();
+            if let Ok(n) = field.parse::<i32>() {
+                SpreadsheetCell::Int(n)
+            } else if let Ok(n) = field.parse::<f64>() {
+                SpreadsheetCell::Float(n)
+            } else {
+                SpreadsheetCell::Text(field.to_string())
+            }
+        })
+        .collect()
+}
+
+//
+// Encapsulating a Vector
+//
+
+// A vector can be wrapped in a struct to only expose the operations we want callers to use
+// Example: a stack, where elements can only be added and removed from the top (last in, first out)
+// Since the inner vector is private, callers can't insert or remove elements from the middle of the stack
+
+pub struct Stack<T> {
+    items: Vec<T>,
+}
+
+impl<T> Stack<T> {
+    pub fn new() -> Stack<T> {
+        Stack { items: Vec::new() }
+    }
+
+    // The top of the stack is the end of the vector, so push() and pop() don't need to shift any elements
+    pub fn push(&mut self, item: T) {
+        self.items.push(item);
+    }
+
+    // Vec::pop() already returns an Option<T>, which is None if the vector is empty
+    pub fn pop(&mut self) -> Option<T> {
+        self.items.pop()
+    }
+
+    // peek() only borrows the top element, so it stays on the stack
+    // The returned reference immutably borrows the stack, so we can't push() or pop() while it is live
+    pub fn peek(&self) -> Option<&T> {
+        self.items.last()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Stack::new()
+    }
+}
+
+//
+// A Fixed-Size Ring Buffer
+//
+
+// A ring buffer keeps only the most recent `cap` elements, which is useful for tracking recent history
+// Once the buffer is full, each push overwrites the oldest element instead of growing the vector
+// `start` is the index of the oldest element, so iteration begins there and wraps around to the front of the vector
+
+pub struct RingBuffer<T> {
+    items: Vec<T>,
+    cap: usize,
+    start: usize,
+}
+
+impl<T> RingBuffer<T> {
+    pub fn new(cap: usize) -> RingBuffer<T> {
+        // A buffer that can't hold anything would silently drop every push, so treat it as a bug in the caller
+        if cap == 0 {
+            panic!("RingBuffer capacity must be greater than 0");
+        }
+
+        RingBuffer {
+            items: Vec::with_capacity(cap),
+            cap,
+            start: 0,
+        }
+    }
+
+    pub fn push(&mut self, item: T) {
+        if self.items.len() < self.cap {
+            self.items.push(item);
+        } else {
+            // Overwrite the oldest element, which makes the next element the oldest one
+            self.items[self.start] = item;
+            self.start = (self.start + 1) % self.cap;
+        }
+    }
+
+    // Chain the two halves of the vector so the elements come out oldest-to-newest
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let (newer, older) = self.items.split_at(self.start);
+        older.iter().chain(newer.iter())
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+//
+// A Least-Recently-Used Cache
+//
+
+// Note: not in book (hash maps are covered later in this chapter)
+// An LRU cache holds up to `cap` key-value pairs, and when it is full, the pair that was used longest ago makes room for the new one
+// Unlike the ring buffer, reading a value with get() counts as using it, so the oldest pair isn't always the one that gets evicted
+// The values are looked up in a HashMap, and `order` keeps the keys from least to most recently used
+// Each key is stored in both, which is why K needs to be Clone
+pub struct LruCache<K: Eq + Hash + Clone, V> {
+    entries: HashMap<K, V>,
+    order: VecDeque<K>,
+    cap: usize,
+}
+
+impl<K: Eq + Hash + Clone, V> LruCache<K, V> {
+    pub fn new(cap: usize) -> LruCache<K, V> {
+        // Every put() would evict the value it just added
+        if cap == 0 {
+            panic!("LruCache capacity must be greater than 0");
+        }
+
+        LruCache {
+            entries: HashMap::with_capacity(cap),
+            order: VecDeque::with_capacity(cap),
+            cap,
+        }
+    }
+
+    // get() takes `&mut self` even though it only reads the value, since it moves the key to the back of `order`
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        if self.entries.contains_key(key) {
+            self.touch(key);
+        }
+        self.entries.get(key)
+    }
+
+    pub fn put(&mut self, key: K, value: V) {
+        if self.entries.contains_key(&key) {
+            // Replacing a value doesn't change the number of pairs, so nothing needs to be evicted
+            self.touch(&key);
+        } else {
+            if self.entries.len() == self.cap {
+                // The front of `order` is the least recently used key
+                if let Some(oldest) = self.order.pop_front() {
+                    self.entries.remove(&oldest);
+                }
+            }
+            self.order.push_back(key.clone());
+        }
+        self.entries.insert(key, value);
+    }
+
+    // Moves `key` to the back of `order`, marking it as the most recently used
+    // Finding the key is O(cap), which is fine for small caches, but a linked list would be needed to make it O(1)
+    fn touch(&mut self, key: &K) {
+        if let Some(position) = self.order.iter().position(|k| k == key) {
+            self.order.remove(position);
+            self.order.push_back(key.clone());
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_checked_in_range() {
+        let v = vec![10, 20, 30];
+
+        assert_eq!(get_checked(&v, 0), Ok(&10));
+        assert_eq!(get_checked(&v, 2), Ok(&30));
+    }
+
+    #[test]
+    fn get_checked_out_of_range() {
+        let v = vec![String::from("Hello"), String::from("world")];
+
+        let err = get_checked(&v, 100).unwrap_err();
+        assert!(err.contains("index 100"));
+        assert!(err.contains("length 2"));
+        // An empty slice has no valid index
+        assert_eq!(
+            get_checked::<i32>(&[], 0),
+            Err(String::from(
+                "index 0 is out of bounds for a slice of length 0"
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_mixed_row() {
+        assert_eq!(
+            parse_row("3,Blue,10.12, -7 ,"),
+            vec![
+                SpreadsheetCell::Int(3),
+                SpreadsheetCell::Text(String::from("Blue")),
+                SpreadsheetCell::Float(10.12),
+                SpreadsheetCell::Int(-7),
+                SpreadsheetCell::Text(String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_row_edge_cases() {
+        // Too large for an i32, so it's read as a float instead
+        assert_eq!(
+            parse_row("3000000000"),
+            vec![SpreadsheetCell::Float(3_000_000_000.0)]
+        );
+        assert_eq!(
+            parse_row("1e3,1.5.2"),
+            vec![
+                SpreadsheetCell::Float(1000.0),
+                SpreadsheetCell::Text(String::from("1.5.2")),
+            ]
+        );
+        // An empty line is a single empty field
+        assert_eq!(parse_row(""), vec![SpreadsheetCell::Text(String::new())]);
+    }
+
+    #[test]
+    fn pops_in_lifo_order() {
+        let mut stack = Stack::new();
+        stack.push(1);
+        stack.push(2);
+        stack.push(3);
+
+        assert_eq!(stack.pop(), Some(3));
+        assert_eq!(stack.pop(), Some(2));
+        assert_eq!(stack.pop(), Some(1));
+        assert_eq!(stack.pop(), None);
+    }
+
+    #[test]
+    fn peek_does_not_remove() {
+        let mut stack = Stack::new();
+        assert_eq!(stack.peek(), None);
+
+        stack.push(String::from("bottom"));
+        stack.push(String::from("top"));
+
+        assert_eq!(stack.peek(), Some(&String::from("top")));
+        assert_eq!(stack.len(), 2);
+        assert_eq!(stack.pop(), Some(String::from("top")));
+        assert_eq!(stack.peek(), Some(&String::from("bottom")));
+    }
+
+    #[test]
+    fn emptiness_transitions() {
+        let mut stack = Stack::new();
+        assert!(stack.is_empty());
+        assert_eq!(stack.len(), 0);
+
+        stack.push('a');
+        assert!(!stack.is_empty());
+        assert_eq!(stack.len(), 1);
+
+        stack.pop();
+        assert!(stack.is_empty());
+        assert_eq!(stack.len(), 0);
+    }
+
+    #[test]
+    fn ring_buffer_keeps_order_before_full() {
+        let mut buffer = RingBuffer::new(3);
+        buffer.push(1);
+        buffer.push(2);
+
+        assert_eq!(buffer.len(), 2);
+        assert_eq!(buffer.iter().collect::<Vec<_>>(), vec![&1, &2]);
+    }
+
+    #[test]
+    fn ring_buffer_overwrites_oldest() {
+        let mut buffer = RingBuffer::new(3);
+        for n in 1..=5 {
+            buffer.push(n);
+        }
+
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.iter().collect::<Vec<_>>(), vec![&3, &4, &5]);
+
+        // Wrapping all the way around the buffer
+        buffer.push(6);
+        buffer.push(7);
+        buffer.push(8);
+        assert_eq!(buffer.iter().collect::<Vec<_>>(), vec![&6, &7, &8]);
+    }
+
+    #[test]
+    fn ring_buffer_of_one() {
+        let mut buffer = RingBuffer::new(1);
+        assert!(buffer.is_empty());
+
+        buffer.push("first");
+        buffer.push("second");
+
+        assert_eq!(buffer.iter().collect::<Vec<_>>(), vec![&"second"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity must be greater than 0")]
+    fn ring_buffer_rejects_zero_capacity() {
+        RingBuffer::<i32>::new(0);
+    }
+
+    #[test]
+    fn lru_evicts_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        cache.put("a", 1);
+        cache.put("b", 2);
+
+        // Reading "a" makes "b" the least recently used
+        assert_eq!(cache.get(&"a"), Some(&1));
+        cache.put("c", 3);
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&"b"), None);
+        assert_eq!(cache.get(&"a"), Some(&1));
+        assert_eq!(cache.get(&"c"), Some(&3));
+
+        // "a" was read before "c", so it goes next
+        cache.put("d", 4);
+        assert_eq!(cache.get(&"a"), None);
+        assert_eq!(cache.get(&"c"), Some(&3));
+        assert_eq!(cache.get(&"d"), Some(&4));
+    }
+
+    #[test]
+    fn lru_put_existing_key_updates_value_and_recency() {
+        let mut cache = LruCache::new(2);
+        cache.put(String::from("a"), 1);
+        cache.put(String::from("b"), 2);
+
+        // Overwriting "a" uses it, so "b" is evicted by the next new key
+        cache.put(String::from("a"), 10);
+        assert_eq!(cache.len(), 2);
+        cache.put(String::from("c"), 3);
+
+        assert_eq!(cache.get(&String::from("a")), Some(&10));
+        assert_eq!(cache.get(&String::from("b")), None);
+    }
+
+    #[test]
+    fn lru_get_missing_key_changes_nothing() {
+        let mut cache = LruCache::new(1);
+        assert!(cache.is_empty());
+        assert_eq!(cache.get(&1), None);
+
+        cache.put(1, "one");
+        cache.put(2, "two");
+
+        assert_eq!(cache.get(&1), None);
+        assert_eq!(cache.get(&2), Some(&"two"));
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity must be greater than 0")]
+    fn lru_rejects_zero_capacity() {
+        LruCache::<i32, i32>::new(0);
+    }
+}
@@ -70,6 +70,7 @@ fn main() {
     //let does_not_exist = &v[100];
     // get() will return None if the index is out of bounds
     let does_not_exist = v.get(100);
+    // Note: not in book - get_checked() in lib.rs returns an Err with the index and length instead
 
     // The borrow checker will enforce the ownership and borrowing rules to ensure that any references to the vector created by & [] and get() will remain valid
     let mut v = vec![1, 2, 3, 4, 5];
@@ -161,6 +162,7 @@ fn main() {
     ];
 
     // Note that we need know the exhaustive set of types that a vector will need to store, or else the enum method won't work (use a trait instead)
+    // Note: not in book - see parse_row() in lib.rs for building a row like this one from a line of text
      */
 
     //
@@ -0,0 +1,157 @@
+use std::error::Error;
+use std::process::ExitCode;
+
+//
+// Retrying Fallible Operations
+//
+
+// Note: not in book
+// Some errors are temporary (e.g. a file that is still being written by another program, or a network hiccup)
+// Instead of giving up on the first Err, we can call the operation again a few times before returning the error
+
+// `op` is an FnMut closure, since operations like reading a file might need to update some state between attempts
+// The first Ok value is returned right away, otherwise the Err from the last attempt is returned
+// Calling retry() with 0 attempts doesn't make sense, since there would be no Result to return, so it panics
+pub fn retry<T, E>(attempts: usize, mut op: impl FnMut() -> Result<T, E>) -> Result<T, E> {
+    if attempts == 0 {
+        panic!("retry needs at least 1 attempt");
+    }
+
+    for _ in 1..attempts {
+        if let Ok(value) = op() {
+            return Ok(value);
+        }
+    }
+    // The last attempt's Result is returned as-is, whether it is Ok or Err
+    op()
+}
+
+//
+// Falling Back to Other Operations
+//
+
+// Note: not in book
+// Sometimes there is more than one way to get a value, e.g. a config file could be in the current directory, the home directory or /etc
+// Each closure is tried in order, and the first Ok value is returned
+// If every closure fails, the Err from the last one is returned
+// The closures are boxed trait objects, since every closure has its own type and a Vec can only hold one type
+pub fn or_else_chain<T, E>(ops: Vec<Box<dyn Fn() -> Result<T, E>>>) -> Result<T, E> {
+    let mut ops = ops.into_iter();
+    // There is no Err to return if the chain is empty
+    let first = ops
+        .next()
+        .expect("or_else_chain needs at least 1 operation");
+
+    // Result::or_else() only calls the closure if the previous Result was an Err
+    ops.fold(first(), |result, op| result.or_else(|_| op()))
+}
+
+//
+// Reporting Errors from main()
+//
+
+// Note: not in book
+// When main() returns Err, Rust prints the error with its Debug format and exits with a non-zero code
+// Returning an ExitCode from main() instead lets us decide how the error is printed
+// run_and_report() runs `f`, prints any error with its Display format to stderr (so it doesn't mix in with normal output),
+// and maps the Result to ExitCode::SUCCESS or ExitCode::FAILURE
+pub fn run_and_report(f: impl FnOnce() -> Result<(), Box<dyn Error>>) -> ExitCode {
+    match f() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_succeeds_on_second_try() {
+        let mut calls = 0;
+        let result: Result<&str, &str> = retry(3, || {
+            calls += 1;
+            if calls < 2 {
+                Err("not yet")
+            } else {
+                Ok("done")
+            }
+        });
+
+        assert_eq!(result, Ok("done"));
+        // The operation isn't called again after it succeeds
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn retry_returns_last_error() {
+        let mut calls = 0;
+        let result: Result<(), String> = retry(3, || {
+            calls += 1;
+            Err(format!("attempt {calls} failed"))
+        });
+
+        assert_eq!(result, Err(String::from("attempt 3 failed")));
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn retry_around_file_open() {
+        let result = retry(2, || std::fs::File::open("does-not-exist.txt"));
+
+        assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least 1 attempt")]
+    fn retry_rejects_zero_attempts() {
+        let _ = retry(0, || Ok::<(), ()>(()));
+    }
+
+    #[test]
+    fn or_else_chain_returns_first_success() {
+        let ops: Vec<Box<dyn Fn() -> Result<&'static str, &'static str>>> = vec![
+            Box::new(|| Err("./config.toml not found")),
+            Box::new(|| Err("~/config.toml not found")),
+            Box::new(|| Ok("/etc/config.toml")),
+            Box::new(|| panic!("should not be called after a success")),
+        ];
+
+        assert_eq!(or_else_chain(ops), Ok("/etc/config.toml"));
+    }
+
+    #[test]
+    fn or_else_chain_returns_last_error() {
+        let ops: Vec<Box<dyn Fn() -> Result<(), String>>> = vec![
+            Box::new(|| Err(String::from("first"))),
+            Box::new(|| Err(String::from("second"))),
+            Box::new(|| Err(String::from("third"))),
+        ];
+
+        assert_eq!(or_else_chain(ops), Err(String::from("third")));
+    }
+
+    #[test]
+    #[should_panic(expected = "at least 1 operation")]
+    fn or_else_chain_rejects_empty_chain() {
+        let _ = or_else_chain::<(), ()>(vec![]);
+    }
+
+    #[test]
+    fn run_and_report_success() {
+        assert_eq!(run_and_report(|| Ok(())), ExitCode::SUCCESS);
+    }
+
+    #[test]
+    fn run_and_report_failure() {
+        let code = run_and_report(|| {
+            std::fs::File::open("does-not-exist.txt")?;
+            Ok(())
+        });
+
+        assert_eq!(code, ExitCode::FAILURE);
+    }
+}
@@ -189,3 +189,13 @@ fn main() -> Result<(), Box<dyn Error>> {
 // When a main function returns a Result<(), E>, the executable will exit with a value of 0 if main returns Ok(())
 // and will exit with a non-zero value if main returns with an Err value
 // The main function may return any types that implement the std::process::Termination trait, which contains a function report() that returns an ExitCode
+
+// Note: not in book - run_and_report() in lib.rs lets main() return an ExitCode while still using ? inside the closure
+/*
+fn main() -> std::process::ExitCode {
+    result::run_and_report(|| {
+        let greeting_file = File::open("hello.txt")?;
+        Ok(())
+    })
+}
+*/